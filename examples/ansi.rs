@@ -0,0 +1,34 @@
+fn main() {
+    use grux::style::{Color, Style, Styled};
+
+    // Create a 4x4 grid of styled cells.
+    // Styled cells are displayed using ANSI escape codes.
+    let mut grid = [[Styled::from(' '); 4]; 4];
+
+    // Draw some shapes using the `Sprite` trait.
+    use grux::art::{BorderRect, FillRect, Sprite};
+
+    // Draw a blue bordered rectangle.
+    let blue = |c| Styled::new(c, Style::new().fg(Color::Blue));
+    let rect = BorderRect::new(4, 4, ['╔', '═', '╗', '║', '║', '╚', '═', '╝'].map(blue));
+    rect.draw_to((0, 0), &mut grid);
+
+    // Draw a bold red filled rectangle in the middle.
+    let rect = FillRect::new(2, 2, Styled::new('█', Style::new().fg(Color::Red).bold()));
+    rect.draw_to((1, 1), &mut grid);
+
+    // Provides a uniform interface for displaying a 2D grid.
+    use grux::DisplayGrid;
+
+    // Print the grid, in color.
+    println!("{}", grid.to_string().unwrap());
+
+    // Print the same grid as a standalone HTML page.
+    use grux::html::HtmlOptions;
+
+    let options = HtmlOptions {
+        standalone: true,
+        ..HtmlOptions::default()
+    };
+    grid.write_html(&mut std::io::stdout(), &options).unwrap();
+}
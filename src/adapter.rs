@@ -0,0 +1,706 @@
+//! Adapters that wrap an existing grid and change how it is written to.
+//!
+//! [`grux::adapter`][`crate::adapter`] provides:
+//! - A toroidal grid, where coordinates wrap around at the edges: [`Wrapping`].
+//! - A grid that records which regions were changed since they were last rendered: [`Tracked`].
+//! - A grid with undo and redo of previous writes: [`History`].
+//! - A rectangular region of a grid, which clips what is drawn to it: [`Viewport`].
+//!
+//! [`Tracked`] and [`History`] require the `alloc` feature.
+
+#[cfg(feature = "alloc")]
+use alloc::{
+    collections::{BTreeMap, VecDeque},
+    vec::Vec,
+};
+#[cfg(feature = "alloc")]
+use core::ops::Range;
+
+#[cfg(feature = "alloc")]
+use crate::art::Sprite;
+#[cfg(feature = "std")]
+use crate::{display::DisplayOptions, DisplayGrid};
+use crate::{FormatGrid, GridReader, GridWriter, Rect};
+
+/// Wraps a fixed-size grid so that coordinates wrap around at the edges (i.e. a torus).
+///
+/// Any position written to is reduced modulo the width and height of the grid, so sprites that are
+/// drawn across an edge continue on the opposite side. This is useful for cellular automata and
+/// games where the world has no edges.
+///
+/// # Examples
+///
+/// ```
+/// # use grux::adapter::Wrapping;
+/// # use grux::art::{Line, Sprite};
+/// let mut grid = Wrapping::from([['.'; 4]; 2]);
+///
+/// // Draw a line that starts near the right edge, and continues on the left.
+/// Line::horizontal(3, '═').draw_to((3, 1), &mut grid);
+///
+/// assert_eq!(grid.into_inner(), [
+///     ['.', '.', '.', '.'],
+///     ['═', '═', '.', '═'],
+/// ]);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Wrapping<G> {
+    grid: G,
+    width: usize,
+    height: usize,
+}
+
+impl<G> Wrapping<G> {
+    /// Wraps the given grid, which is assumed to be (at least) `width` by `height` in size.
+    ///
+    /// # Panics
+    ///
+    /// If the width or height is zero.
+    #[must_use]
+    pub fn new(grid: G, width: usize, height: usize) -> Self {
+        assert!(width > 0, "Width must be at least 1");
+        assert!(height > 0, "Height must be at least 1");
+        Self {
+            grid,
+            width,
+            height,
+        }
+    }
+
+    /// Returns the `(width, height)` that coordinates wrap around at.
+    #[must_use]
+    pub fn size(&self) -> (usize, usize) {
+        (self.width, self.height)
+    }
+
+    /// Returns a reference to the underlying grid.
+    #[must_use]
+    pub fn inner(&self) -> &G {
+        &self.grid
+    }
+
+    /// Returns a mutable reference to the underlying grid.
+    #[must_use]
+    pub fn inner_mut(&mut self) -> &mut G {
+        &mut self.grid
+    }
+
+    /// Unwraps the underlying grid.
+    #[must_use]
+    pub fn into_inner(self) -> G {
+        self.grid
+    }
+}
+
+/// Wraps a fixed-size nested array, using the dimensions of the array.
+impl<const W: usize, const H: usize, T> From<[[T; W]; H]> for Wrapping<[[T; W]; H]> {
+    fn from(grid: [[T; W]; H]) -> Self {
+        Self::new(grid, W, H)
+    }
+}
+
+impl<G> GridWriter for Wrapping<G>
+where
+    G: GridWriter,
+{
+    type Element = G::Element;
+
+    /// Sets the element at the given `(x, y)` position, wrapped around the edges of the grid.
+    fn set(&mut self, position: (usize, usize), element: Self::Element) {
+        let (x, y) = position;
+        self.grid.set((x % self.width, y % self.height), element);
+    }
+}
+
+impl<G> GridReader for Wrapping<G>
+where
+    G: GridReader,
+{
+    type Element = G::Element;
+
+    /// Returns the element at the given `(x, y)` position, wrapped around the edges of the grid.
+    fn get(&self, position: (usize, usize)) -> Option<&Self::Element> {
+        let (x, y) = position;
+        self.grid.get((x % self.width, y % self.height))
+    }
+}
+
+#[cfg(feature = "std")]
+impl<G> DisplayGrid for Wrapping<G>
+where
+    G: DisplayGrid,
+{
+    fn write_to(&self, stream: &mut impl std::io::Write) -> std::io::Result<()> {
+        self.grid.write_to(stream)
+    }
+
+    fn write_with(
+        &self,
+        stream: &mut impl std::io::Write,
+        options: &DisplayOptions,
+    ) -> std::io::Result<()> {
+        self.grid.write_with(stream, options)
+    }
+}
+
+impl<G> FormatGrid for Wrapping<G>
+where
+    G: FormatGrid,
+{
+    fn write_fmt_to(&self, out: &mut impl core::fmt::Write) -> core::fmt::Result {
+        self.grid.write_fmt_to(out)
+    }
+}
+
+/// Wraps a grid so that positions are relative to a rectangle of it, and clipped to the rectangle.
+///
+/// Writes outside the size of the rectangle are ignored (and reads return `None`), so a sprite
+/// that is too large for a panel does not spill over into its neighbours. Rectangles are usually
+/// computed with [`Layout`][`crate::layout::Layout`].
+///
+/// # Examples
+///
+/// ```
+/// # use grux::Rect;
+/// # use grux::adapter::Viewport;
+/// # use grux::art::{Line, Sprite};
+/// let mut grid = [['.'; 4]; 2];
+///
+/// // Draw a line that is longer than the viewport.
+/// let mut viewport = Viewport::new(&mut grid, Rect::new(1, 1, 2, 1));
+/// Line::horizontal(4, '═').draw_to((0, 0), &mut viewport);
+///
+/// assert_eq!(grid, [
+///     ['.', '.', '.', '.'],
+///     ['.', '═', '═', '.'],
+/// ]);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Viewport<G> {
+    grid: G,
+    rect: Rect,
+}
+
+impl<G> Viewport<G> {
+    /// Wraps the given grid, with positions relative to the given rectangle.
+    #[must_use]
+    pub fn new(grid: G, rect: Rect) -> Self {
+        Self { grid, rect }
+    }
+
+    /// Returns the rectangle of the underlying grid that is written to.
+    #[must_use]
+    pub fn rect(&self) -> Rect {
+        self.rect
+    }
+
+    /// Returns the `(width, height)` of the viewport.
+    #[must_use]
+    pub fn size(&self) -> (usize, usize) {
+        (self.rect.width, self.rect.height)
+    }
+
+    /// Returns a reference to the underlying grid.
+    #[must_use]
+    pub fn inner(&self) -> &G {
+        &self.grid
+    }
+
+    /// Returns a mutable reference to the underlying grid.
+    #[must_use]
+    pub fn inner_mut(&mut self) -> &mut G {
+        &mut self.grid
+    }
+
+    /// Unwraps the underlying grid.
+    #[must_use]
+    pub fn into_inner(self) -> G {
+        self.grid
+    }
+
+    /// Returns the position in the underlying grid, or `None` if it is outside the viewport.
+    fn translate(&self, position: (usize, usize)) -> Option<(usize, usize)> {
+        let (x, y) = position;
+        (x < self.rect.width && y < self.rect.height).then(|| (self.rect.x + x, self.rect.y + y))
+    }
+}
+
+impl<G> GridWriter for Viewport<G>
+where
+    G: GridWriter,
+{
+    type Element = G::Element;
+
+    /// Sets the element at the given `(x, y)` position, relative to the viewport; positions
+    /// outside the viewport are ignored.
+    fn set(&mut self, position: (usize, usize), element: Self::Element) {
+        if let Some(position) = self.translate(position) {
+            self.grid.set(position, element);
+        }
+    }
+}
+
+impl<G> GridReader for Viewport<G>
+where
+    G: GridReader,
+{
+    type Element = G::Element;
+
+    /// Returns the element at the given `(x, y)` position, relative to the viewport, or `None` if
+    /// it is outside the viewport.
+    fn get(&self, position: (usize, usize)) -> Option<&Self::Element> {
+        self.grid.get(self.translate(position)?)
+    }
+}
+
+/// Wraps a grid and records which cells were written to, i.e. "dirty rectangles".
+///
+/// Every call to [`GridWriter::set`] marks the cell as dirty. Dirty cells are coalesced per row
+/// into the fewest possible rectangles (each one row high), so a renderer can redraw only the parts
+/// of the grid that changed since the last frame, and then call [`Tracked::take_dirty`].
+///
+/// # Examples
+///
+/// ```
+/// # use grux::Rect;
+/// # use grux::adapter::Tracked;
+/// # use grux::art::{FillRect, Sprite};
+/// let mut grid = Tracked::new([[' '; 8]; 4]);
+///
+/// FillRect::new(3, 2, '█').draw_to((1, 1), &mut grid);
+///
+/// assert_eq!(grid.take_dirty(), vec![Rect::new(1, 1, 3, 1), Rect::new(1, 2, 3, 1)]);
+/// assert!(!grid.is_dirty());
+/// ```
+#[cfg(feature = "alloc")]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Tracked<G> {
+    grid: G,
+    dirty: BTreeMap<usize, Vec<Range<usize>>>,
+}
+
+#[cfg(feature = "alloc")]
+impl<G> Tracked<G> {
+    /// Wraps the given grid, with nothing marked as dirty.
+    #[must_use]
+    pub fn new(grid: G) -> Self {
+        Self {
+            grid,
+            dirty: BTreeMap::new(),
+        }
+    }
+
+    /// Returns `true` if any cell was written to since the last call to [`Tracked::take_dirty`].
+    #[must_use]
+    pub fn is_dirty(&self) -> bool {
+        !self.dirty.is_empty()
+    }
+
+    /// Marks every cell in the given rectangle as dirty, e.g. to force a full redraw.
+    pub fn mark_dirty(&mut self, rect: Rect) {
+        for y in rect.y..rect.bottom() {
+            for x in rect.x..rect.right() {
+                self.mark((x, y));
+            }
+        }
+    }
+
+    /// Returns the dirty rectangles, ordered by row and then column, and clears them.
+    ///
+    /// Each rectangle is one row high, and spans a contiguous run of dirty cells.
+    pub fn take_dirty(&mut self) -> Vec<Rect> {
+        core::mem::take(&mut self.dirty)
+            .into_iter()
+            .flat_map(|(y, runs)| {
+                runs.into_iter()
+                    .map(move |run| Rect::new(run.start, y, run.len(), 1))
+            })
+            .collect()
+    }
+
+    /// Returns a reference to the underlying grid.
+    #[must_use]
+    pub fn inner(&self) -> &G {
+        &self.grid
+    }
+
+    /// Returns a mutable reference to the underlying grid.
+    ///
+    /// > ⓘ **NOTE**: Writes made directly to the underlying grid are _not_ tracked.
+    #[must_use]
+    pub fn inner_mut(&mut self) -> &mut G {
+        &mut self.grid
+    }
+
+    /// Unwraps the underlying grid, discarding the dirty rectangles.
+    #[must_use]
+    pub fn into_inner(self) -> G {
+        self.grid
+    }
+
+    fn mark(&mut self, position: (usize, usize)) {
+        let (x, y) = position;
+        let runs = self.dirty.entry(y).or_default();
+
+        // Find the first run that ends at or after the position; it's the only one that can be
+        // extended (or already contains the position), otherwise a new run is inserted before it.
+        let i = runs.partition_point(|run| run.end < x);
+        if i < runs.len() && runs[i].start <= x + 1 {
+            runs[i].start = runs[i].start.min(x);
+            runs[i].end = runs[i].end.max(x + 1);
+
+            // Extending the run may have closed the gap to the next run.
+            if i + 1 < runs.len() && runs[i + 1].start <= runs[i].end {
+                runs[i].end = runs[i].end.max(runs[i + 1].end);
+                runs.remove(i + 1);
+            }
+        } else {
+            runs.insert(i, x..x + 1);
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<G> GridWriter for Tracked<G>
+where
+    G: GridWriter,
+{
+    type Element = G::Element;
+
+    /// Sets the element at the given `(x, y)` position, and marks the cell as dirty.
+    fn set(&mut self, position: (usize, usize), element: Self::Element) {
+        self.grid.set(position, element);
+        self.mark(position);
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<G> GridReader for Tracked<G>
+where
+    G: GridReader,
+{
+    type Element = G::Element;
+
+    fn get(&self, position: (usize, usize)) -> Option<&Self::Element> {
+        self.grid.get(position)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<G> DisplayGrid for Tracked<G>
+where
+    G: DisplayGrid,
+{
+    fn write_to(&self, stream: &mut impl std::io::Write) -> std::io::Result<()> {
+        self.grid.write_to(stream)
+    }
+
+    fn write_with(
+        &self,
+        stream: &mut impl std::io::Write,
+        options: &DisplayOptions,
+    ) -> std::io::Result<()> {
+        self.grid.write_with(stream, options)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<G> FormatGrid for Tracked<G>
+where
+    G: FormatGrid,
+{
+    fn write_fmt_to(&self, out: &mut impl core::fmt::Write) -> core::fmt::Result {
+        self.grid.write_fmt_to(out)
+    }
+}
+
+/// A single cell that was written to, with the elements before and after the write.
+#[cfg(feature = "alloc")]
+#[derive(Clone, Debug)]
+struct Change<T> {
+    position: (usize, usize),
+    before: T,
+    after: T,
+}
+
+/// Wraps a grid and records every write, so that writes can be undone and redone.
+///
+/// Each call to [`GridWriter::set`] captures the previous element of the cell. Writes are grouped
+/// into undo steps:
+///
+/// - Outside of a transaction, each write is its own step.
+/// - Between [`History::begin`] and [`History::commit`], all writes are a single step.
+/// - [`History::draw`] draws a sprite as a single step.
+///
+/// Cells that did not exist before a write (e.g. when a nested vector grows) are restored to the
+/// default element when undone.
+///
+/// # Memory
+///
+/// By default every step is kept. Use [`History::with_budget`] to limit the number of cell writes
+/// that are recorded; when exceeded, the oldest steps are forgotten.
+///
+/// # Examples
+///
+/// ```
+/// # use grux::adapter::History;
+/// # use grux::art::{FillRect, Line};
+/// let mut grid = History::new([['.'; 3]; 2]);
+///
+/// grid.draw((0, 0), &FillRect::new(3, 2, '█'));
+/// grid.draw((0, 1), &Line::horizontal(3, '═'));
+///
+/// grid.undo();
+/// assert_eq!(grid.inner(), &[['█'; 3]; 2]);
+///
+/// grid.undo();
+/// assert_eq!(grid.inner(), &[['.'; 3]; 2]);
+///
+/// grid.redo();
+/// assert_eq!(grid.inner(), &[['█'; 3]; 2]);
+/// ```
+#[cfg(feature = "alloc")]
+#[derive(Clone, Debug)]
+pub struct History<G>
+where
+    G: GridWriter,
+{
+    grid: G,
+    undo: VecDeque<Vec<Change<G::Element>>>,
+    redo: Vec<Vec<Change<G::Element>>>,
+    pending: Vec<Change<G::Element>>,
+    depth: usize,
+    budget: usize,
+    recorded: usize,
+}
+
+#[cfg(feature = "alloc")]
+impl<G> History<G>
+where
+    G: GridWriter,
+{
+    /// Wraps the given grid, with an empty history and no memory budget.
+    #[must_use]
+    pub fn new(grid: G) -> Self {
+        Self {
+            grid,
+            undo: VecDeque::new(),
+            redo: Vec::new(),
+            pending: Vec::new(),
+            depth: 0,
+            budget: usize::MAX,
+            recorded: 0,
+        }
+    }
+
+    /// Limits the history to recording at most the given number of cell writes.
+    ///
+    /// When a new step would exceed the budget, the oldest steps are forgotten; a single step that
+    /// is larger than the budget can't be undone.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use grux::GridWriter;
+    /// # use grux::adapter::History;
+    /// let mut grid = History::new([[0; 3]; 1]).with_budget(2);
+    ///
+    /// grid.set((0, 0), 1);
+    /// grid.set((1, 0), 2);
+    /// grid.set((2, 0), 3);
+    ///
+    /// assert!(grid.undo());
+    /// assert!(grid.undo());
+    /// assert!(!grid.undo());
+    /// assert_eq!(grid.inner(), &[[1, 0, 0]]);
+    /// ```
+    #[must_use]
+    pub fn with_budget(mut self, budget: usize) -> Self {
+        self.budget = budget;
+        self.enforce_budget();
+        self
+    }
+
+    /// Returns `true` if there is a step that can be undone.
+    #[must_use]
+    pub fn can_undo(&self) -> bool {
+        !self.undo.is_empty() || !self.pending.is_empty()
+    }
+
+    /// Returns `true` if there is an undone step that can be redone.
+    #[must_use]
+    pub fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+
+    /// Starts a transaction; every write until the matching [`History::commit`] is one step.
+    ///
+    /// Transactions can be nested, in which case the outermost transaction is the step.
+    pub fn begin(&mut self) {
+        self.depth += 1;
+    }
+
+    /// Ends a transaction started with [`History::begin`].
+    ///
+    /// # Panics
+    ///
+    /// If there is no transaction in progress.
+    pub fn commit(&mut self) {
+        assert!(self.depth > 0, "No transaction in progress");
+        self.depth -= 1;
+        if self.depth == 0 {
+            self.push_pending();
+        }
+    }
+
+    /// Draws the given sprite at the given `(x, y)` position as a single step.
+    pub fn draw(&mut self, position: (usize, usize), sprite: &impl Sprite<Element = G::Element>)
+    where
+        Self: GridWriter<Element = G::Element>,
+    {
+        self.begin();
+        sprite.draw_to(position, self);
+        self.commit();
+    }
+
+    /// Undoes the most recent step, returning `false` if there was nothing to undo.
+    ///
+    /// If a transaction is in progress, the writes so far are recorded as a step first; the
+    /// transaction stays open, and later writes become another step when it is committed.
+    pub fn undo(&mut self) -> bool
+    where
+        G::Element: Clone,
+    {
+        self.push_pending();
+
+        let Some(step) = self.undo.pop_back() else {
+            return false;
+        };
+        for change in step.iter().rev() {
+            self.grid.set(change.position, change.before.clone());
+        }
+        self.redo.push(step);
+        true
+    }
+
+    /// Redoes the most recently undone step, returning `false` if there was nothing to redo.
+    ///
+    /// Like [`History::undo`], the writes so far in a transaction in progress are recorded as a
+    /// step first, which (as with any other write) means there is nothing left to redo.
+    pub fn redo(&mut self) -> bool
+    where
+        G::Element: Clone,
+    {
+        self.push_pending();
+        let Some(step) = self.redo.pop() else {
+            return false;
+        };
+        for change in &step {
+            self.grid.set(change.position, change.after.clone());
+        }
+        self.undo.push_back(step);
+        true
+    }
+
+    /// Forgets every step, without changing the grid.
+    pub fn clear(&mut self) {
+        self.undo.clear();
+        self.redo.clear();
+        self.pending.clear();
+        self.recorded = 0;
+    }
+
+    /// Returns a reference to the underlying grid.
+    #[must_use]
+    pub fn inner(&self) -> &G {
+        &self.grid
+    }
+
+    /// Unwraps the underlying grid, discarding the history.
+    #[must_use]
+    pub fn into_inner(self) -> G {
+        self.grid
+    }
+
+    fn push_pending(&mut self) {
+        if self.pending.is_empty() {
+            return;
+        }
+        self.recorded -= self.redo.drain(..).map(|step| step.len()).sum::<usize>();
+        self.undo.push_back(core::mem::take(&mut self.pending));
+        self.enforce_budget();
+    }
+
+    fn enforce_budget(&mut self) {
+        while self.recorded > self.budget {
+            match self.undo.pop_front() {
+                Some(step) => self.recorded -= step.len(),
+                None => break,
+            }
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<G> GridWriter for History<G>
+where
+    G: GridWriter + GridReader<Element = <G as GridWriter>::Element>,
+    <G as GridWriter>::Element: Clone + Default,
+{
+    type Element = <G as GridWriter>::Element;
+
+    /// Sets the element at the given `(x, y)` position, recording the previous element.
+    fn set(&mut self, position: (usize, usize), element: Self::Element) {
+        let before = self.grid.get(position).cloned().unwrap_or_default();
+        self.grid.set(position, element.clone());
+        self.pending.push(Change {
+            position,
+            before,
+            after: element,
+        });
+        self.recorded += 1;
+        if self.depth == 0 {
+            self.push_pending();
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<G> GridReader for History<G>
+where
+    G: GridWriter + GridReader,
+{
+    type Element = <G as GridReader>::Element;
+
+    fn get(&self, position: (usize, usize)) -> Option<&Self::Element> {
+        self.grid.get(position)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<G> DisplayGrid for History<G>
+where
+    G: GridWriter + DisplayGrid,
+{
+    fn write_to(&self, stream: &mut impl std::io::Write) -> std::io::Result<()> {
+        self.grid.write_to(stream)
+    }
+
+    fn write_with(
+        &self,
+        stream: &mut impl std::io::Write,
+        options: &DisplayOptions,
+    ) -> std::io::Result<()> {
+        self.grid.write_with(stream, options)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<G> FormatGrid for History<G>
+where
+    G: GridWriter + FormatGrid,
+{
+    fn write_fmt_to(&self, out: &mut impl core::fmt::Write) -> core::fmt::Result {
+        self.grid.write_fmt_to(out)
+    }
+}
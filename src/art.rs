@@ -3,17 +3,62 @@
 //! [`grux::art`][`crate::art`] provides:
 //! - A uniform interface for drawing art to a 2D grid: [`Sprite`].
 //! - Built-in art types e.g. [`Line`], [`FilledRect`], [`BorderRect`].
+//! - A canvas of pixels at a higher resolution than cells, drawn as braille: [`BrailleCanvas`].
+//! - A canvas of colored pixels, drawn as half blocks or quadrants: [`BlockCanvas`].
+//! - Charts of values, drawn with eighth blocks: [`Sparkline`], [`BarChart`].
+//! - Line and scatter plots with axes and a legend: [`Plot`].
+//! - Progress indicators: [`ProgressBar`], [`Gauge`].
+//! - Tables of text with box-drawn separators: [`Table`].
+//! - Nested rows, columns and stacks of sprites: [`Flex`].
+//! - Scrollable lists and menus with a selected item: [`List`].
 //!
 //! > ⓘ **NOTE**: The art types in this module are _not_ limited to ASCII characters.
 //! >
 //! > For example, you can use [`Line`] to draw a line of unicode characters, or even a line of a
 //! > more complex type; for example a custom `Cell` struct in your own data structure. ASCII is
 //! > just a way to understand the way the sprites are drawn.
+//!
+//! With the `serde` feature, the built-in art types can be serialized and deserialized, e.g. to
+//! save and load UI layouts.
 
-use std::fmt::Display;
+use core::fmt::Display;
 
 use crate::GridWriter;
 
+#[cfg(feature = "alloc")]
+mod block;
+#[cfg(feature = "alloc")]
+mod braille;
+#[cfg(feature = "alloc")]
+mod chart;
+#[cfg(feature = "alloc")]
+mod flex;
+#[cfg(feature = "alloc")]
+mod list;
+#[cfg(feature = "alloc")]
+mod plot;
+#[cfg(feature = "alloc")]
+mod progress;
+#[cfg(feature = "alloc")]
+mod table;
+
+#[cfg(feature = "alloc")]
+pub use block::{BlockCanvas, BlockResolution, StyledBlocks};
+#[cfg(feature = "alloc")]
+pub use braille::BrailleCanvas;
+#[cfg(feature = "alloc")]
+pub use chart::{BarChart, Sparkline};
+#[cfg(feature = "alloc")]
+pub use flex::{Flex, FlexAlign, FlexJustify};
+#[cfg(feature = "alloc")]
+pub use list::{List, ListState};
+#[cfg(feature = "alloc")]
+pub use plot::{Marker, Plot, Series};
+#[cfg(feature = "alloc")]
+pub use progress::{Gauge, ProgressBar};
+#[cfg(feature = "alloc")]
+pub use table::{Alignment, ColumnWidth, Table};
+
 /// A trait for types that can be drawn to a 2D grid.
 ///
 /// # Examples
@@ -98,6 +143,7 @@ pub trait Sprite {
 ///     ['═', '═', '═']
 /// ]);
 /// ```
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Line<T: Display> {
     length: usize,
     render: T,
@@ -105,7 +151,9 @@ pub struct Line<T: Display> {
 }
 
 /// Options for drawing a line to a 2D grid.
-enum Orientation {
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Orientation {
     /// Left to right.
     Horizontal,
 
@@ -114,6 +162,16 @@ enum Orientation {
 }
 
 impl<T: Display> Line<T> {
+    /// Configures a line of the given orientation and length.
+    #[must_use]
+    pub fn new(orientation: Orientation, length: usize, render: T) -> Self {
+        Self {
+            length,
+            render,
+            orientation,
+        }
+    }
+
     /// Configures a horizontal line of the given length.
     #[must_use]
     pub fn horizontal(length: usize, render: T) -> Self {
@@ -133,6 +191,12 @@ impl<T: Display> Line<T> {
             orientation: Orientation::Vertical,
         }
     }
+
+    /// Returns the orientation of the line.
+    #[must_use]
+    pub fn orientation(&self) -> Orientation {
+        self.orientation
+    }
 }
 
 impl<T: Display + Clone> Sprite for Line<T> {
@@ -192,6 +256,7 @@ impl<T: Display + Clone> Sprite for Line<T> {
 ///     [' ', ' ', ' ', ' '],
 /// ]);
 /// ```
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct FillRect<T: Display> {
     width: usize,
     height: usize,
@@ -251,6 +316,10 @@ impl<T: Display + Clone> Sprite for FillRect<T> {
 ///     ['╚', '═', '═', '╝'],
 /// ]);
 /// ```
+///
+/// With the `serde` feature, deserializing a rectangle with a width or height less than 2 fails,
+/// rather than panicking like [`BorderRect::new`].
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct BorderRect<T: Display> {
     width: usize,
     height: usize,
@@ -284,6 +353,34 @@ impl<T: Display> BorderRect<T> {
         }
     }
 }
+#[cfg(feature = "serde")]
+impl<'de, T> serde::Deserialize<'de> for BorderRect<T>
+where
+    T: Display + serde::Deserialize<'de>,
+{
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(serde::Deserialize)]
+        #[serde(rename = "BorderRect")]
+        struct Fields<T> {
+            width: usize,
+            height: usize,
+            render: [T; 8],
+        }
+
+        let Fields {
+            width,
+            height,
+            render,
+        } = Fields::deserialize(deserializer)?;
+        if width < 2 || height < 2 {
+            return Err(serde::de::Error::custom(
+                "width and height must be at least 2",
+            ));
+        }
+        Ok(Self::new(width, height, render))
+    }
+}
+
 impl<T: Display + Clone> BorderRect<T> {
     fn top_left(&self) -> T {
         self.render[0].clone()
@@ -0,0 +1,372 @@
+use alloc::{vec, vec::Vec};
+use core::cmp::Reverse;
+
+use crate::{
+    art::Sprite,
+    grid::Grid,
+    style::{Color, Style, Styled},
+    GridWriter,
+};
+
+/// The block characters for each combination of quadrants, indexed by a bit mask of the top-left
+/// (`1`), top-right (`2`), bottom-left (`4`) and bottom-right (`8`) quadrants.
+const QUADRANTS: [char; 16] = [
+    ' ', '▘', '▝', '▀', '▖', '▌', '▞', '▛', '▗', '▚', '▐', '▜', '▄', '▙', '▟', '█',
+];
+
+/// How many pixels a [`BlockCanvas`] draws per cell.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum BlockResolution {
+    /// 1×2 pixels per cell, drawn with `▀`, `▄` and `█`.
+    Half,
+
+    /// 2×2 pixels per cell, drawn with half blocks and quadrant characters, e.g. `▌` and `▚`.
+    #[default]
+    Quadrant,
+}
+
+impl BlockResolution {
+    /// Returns the `(x, y)` offset and quadrant bits of each pixel within a cell.
+    const fn pixels(self) -> &'static [((usize, usize), u8)] {
+        match self {
+            BlockResolution::Half => &[((0, 0), 0b0011), ((0, 1), 0b1100)],
+            BlockResolution::Quadrant => &[
+                ((0, 0), 0b0001),
+                ((1, 0), 0b0010),
+                ((0, 1), 0b0100),
+                ((1, 1), 0b1000),
+            ],
+        }
+    }
+
+    /// Returns the number of pixel columns per cell.
+    const fn columns(self) -> usize {
+        match self {
+            BlockResolution::Half => 1,
+            BlockResolution::Quadrant => 2,
+        }
+    }
+}
+
+/// A canvas of colored pixels drawn as block characters, i.e. 1×2 or 2×2 pixels per cell.
+///
+/// Pixels are addressed by `(x, y)`, from the top-left corner, and are either a [`Color`] or unset.
+/// Pixels outside the canvas are ignored.
+///
+/// The canvas can be drawn as characters, where every set pixel is filled in regardless of its
+/// color, or as [`Styled`] cells through [`BlockCanvas::styled`], where each cell uses the glyph
+/// and the foreground and background colors that best fit its pixels.
+///
+/// # Examples
+///
+/// ```
+/// # use grux::art::{BlockCanvas, BlockResolution, Sprite};
+/// # use grux::style::Color;
+/// let mut canvas = BlockCanvas::new(2, 1, BlockResolution::Quadrant);
+/// canvas.set_pixel((0, 0), Color::Red);
+/// canvas.set_pixel((1, 1), Color::Red);
+/// canvas.set_pixel((2, 0), Color::Blue);
+/// canvas.set_pixel((2, 1), Color::Blue);
+///
+/// let mut grid = [[' '; 2]; 1];
+/// canvas.draw_to((0, 0), &mut grid);
+///
+/// assert_eq!(grid, [['▚', '▌']]);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct BlockCanvas {
+    width: usize,
+    height: usize,
+    resolution: BlockResolution,
+    pixels: Vec<Option<Color>>,
+}
+
+impl BlockCanvas {
+    /// Creates an empty canvas of the given size, in cells.
+    #[must_use]
+    pub fn new(width: usize, height: usize, resolution: BlockResolution) -> Self {
+        Self {
+            width,
+            height,
+            resolution,
+            pixels: vec![None; width * resolution.columns() * height * 2],
+        }
+    }
+
+    /// Creates a canvas from a grid of pixels, e.g. an image.
+    ///
+    /// The canvas is as many cells as needed to fit the pixels; if the grid does not fill the last
+    /// row or column of cells, the remaining pixels are unset.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use grux::art::{BlockCanvas, BlockResolution, Sprite};
+    /// # use grux::grid::Grid;
+    /// # use grux::style::Color;
+    /// let pixels = Grid::from_cells(1, 3, vec![Some(Color::Red), None, Some(Color::Red)]);
+    /// let canvas = BlockCanvas::from_pixels(&pixels, BlockResolution::Half);
+    ///
+    /// let mut grid = [[' '; 1]; 2];
+    /// canvas.draw_to((0, 0), &mut grid);
+    ///
+    /// assert_eq!(grid, [['▀'], ['▀']]);
+    /// ```
+    #[must_use]
+    pub fn from_pixels(pixels: &Grid<Option<Color>>, resolution: BlockResolution) -> Self {
+        let (width, height) = pixels.size();
+        let mut canvas = Self::new(
+            width.div_ceil(resolution.columns()),
+            height.div_ceil(2),
+            resolution,
+        );
+        for (y, row) in pixels.rows().enumerate() {
+            for (x, pixel) in row.iter().enumerate() {
+                if let Some(color) = *pixel {
+                    canvas.set_pixel((x, y), color);
+                }
+            }
+        }
+        canvas
+    }
+
+    /// Returns the resolution of the canvas.
+    #[must_use]
+    pub fn resolution(&self) -> BlockResolution {
+        self.resolution
+    }
+
+    /// Returns the `(width, height)` of the canvas, in pixels.
+    #[must_use]
+    pub fn pixel_size(&self) -> (usize, usize) {
+        (self.width * self.resolution.columns(), self.height * 2)
+    }
+
+    /// Returns the color of the pixel at the given `(x, y)` position, or `None` if it is unset.
+    ///
+    /// Pixels outside the canvas are never set.
+    #[must_use]
+    pub fn get_pixel(&self, position: (usize, usize)) -> Option<Color> {
+        self.index(position).and_then(|index| self.pixels[index])
+    }
+
+    /// Sets the pixel at the given `(x, y)` position to a color, if it is inside the canvas.
+    pub fn set_pixel(&mut self, position: (usize, usize), color: Color) {
+        if let Some(index) = self.index(position) {
+            self.pixels[index] = Some(color);
+        }
+    }
+
+    /// Clears the pixel at the given `(x, y)` position, if it is inside the canvas.
+    pub fn unset_pixel(&mut self, position: (usize, usize)) {
+        if let Some(index) = self.index(position) {
+            self.pixels[index] = None;
+        }
+    }
+
+    /// Clears every pixel.
+    pub fn clear(&mut self) {
+        self.pixels.fill(None);
+    }
+
+    /// Returns the block character of the cell at the given `(x, y)` position, with every set
+    /// pixel filled in, or `None` if it is outside the canvas.
+    #[must_use]
+    pub fn cell(&self, position: (usize, usize)) -> Option<char> {
+        let (x, y) = position;
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+        let mask = self
+            .cell_pixels(position)
+            .filter(|(pixel, _)| pixel.is_some())
+            .fold(0, |mask, (_, bits)| mask | bits);
+        Some(QUADRANTS[usize::from(mask)])
+    }
+
+    /// Returns the styled block character of the cell at the given `(x, y)` position, or `None`
+    /// if it is outside the canvas.
+    ///
+    /// A cell can only show two colors. The colors of its pixels are ranked by how many pixels
+    /// have them, with ties going to the color of the earlier pixel (left to right, top to
+    /// bottom), and unset pixels ranked as a color of their own:
+    ///
+    /// - If the top two are both set colors, the first is the foreground and the second is the
+    ///   background. Pixels of any other color take whichever of the two is closest (the
+    ///   foreground, if equally close), and unset pixels show the background color.
+    /// - Otherwise, i.e. if an unset pixel ranks in the top two or there is only one color, the
+    ///   background is the terminal's default, the foreground is the highest ranked set color,
+    ///   and every set pixel is drawn in the foreground color.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use grux::art::{BlockCanvas, BlockResolution};
+    /// # use grux::style::{Color, Style, Styled};
+    /// let mut canvas = BlockCanvas::new(1, 1, BlockResolution::Half);
+    /// canvas.set_pixel((0, 0), Color::Red);
+    /// canvas.set_pixel((0, 1), Color::Blue);
+    ///
+    /// let style = Style::new().fg(Color::Red).bg(Color::Blue);
+    /// assert_eq!(canvas.styled_cell((0, 0)), Some(Styled::new('▀', style)));
+    /// ```
+    #[must_use]
+    pub fn styled_cell(&self, position: (usize, usize)) -> Option<Styled> {
+        let (x, y) = position;
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+
+        // The distinct colors of the cell, with how many pixels have each, in order of appearance.
+        let mut counts: [(Option<Color>, usize); 4] = [(None, 0); 4];
+        let mut distinct = 0;
+        for (pixel, _) in self.cell_pixels(position) {
+            match counts[..distinct]
+                .iter_mut()
+                .find(|(color, _)| *color == pixel)
+            {
+                Some((_, count)) => *count += 1,
+                None => {
+                    counts[distinct] = (pixel, 1);
+                    distinct += 1;
+                }
+            }
+        }
+        let mut ranked = counts[..distinct].to_vec();
+        ranked.sort_by_key(|&(_, count)| Reverse(count));
+
+        // Unset pixels are always the background, so that they show the terminal's default.
+        let (foreground, background) = match (ranked[0].0, ranked.get(1).map(|(color, _)| *color)) {
+            (None, second) => (second.flatten(), None),
+            (first, Some(None) | None) => (first, None),
+            (first, Some(second)) => (first, second),
+        };
+
+        let mut mask = 0;
+        for (pixel, bits) in self.cell_pixels(position) {
+            let is_foreground = match (pixel, foreground, background) {
+                (_, _, _) if pixel == background => false,
+                (Some(_), Some(_), None) => true,
+                (Some(color), Some(fg), Some(bg)) => distance(color, fg) <= distance(color, bg),
+                _ => false,
+            };
+            if is_foreground {
+                mask |= bits;
+            }
+        }
+
+        let style = Style {
+            foreground: foreground.filter(|_| mask != 0),
+            background,
+            ..Style::new()
+        };
+        Some(Styled::new(QUADRANTS[usize::from(mask)], style))
+    }
+
+    /// Returns a sprite that draws the canvas as styled cells; see [`BlockCanvas::styled_cell`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use grux::art::{BlockCanvas, BlockResolution, Sprite};
+    /// # use grux::style::{Color, Style, Styled};
+    /// let mut canvas = BlockCanvas::new(2, 1, BlockResolution::Half);
+    /// canvas.set_pixel((1, 1), Color::Green);
+    ///
+    /// let mut grid = [[Styled::from('.'); 2]; 1];
+    /// canvas.styled().draw_to((0, 0), &mut grid);
+    ///
+    /// let green = Style::new().fg(Color::Green);
+    /// assert_eq!(grid, [[Styled::from(' '), Styled::new('▄', green)]]);
+    /// ```
+    #[must_use]
+    pub fn styled(&self) -> StyledBlocks<'_> {
+        StyledBlocks { canvas: self }
+    }
+
+    /// Returns the pixels of a cell, with their quadrant bits.
+    fn cell_pixels(
+        &self,
+        position: (usize, usize),
+    ) -> impl Iterator<Item = (Option<Color>, u8)> + '_ {
+        let columns = self.resolution.columns();
+        let (x, y) = (position.0 * columns, position.1 * 2);
+        self.resolution
+            .pixels()
+            .iter()
+            .map(move |&((dx, dy), bits)| (self.get_pixel((x + dx, y + dy)), bits))
+    }
+
+    /// Returns the index of the pixel.
+    fn index(&self, position: (usize, usize)) -> Option<usize> {
+        let (x, y) = position;
+        let (width, height) = self.pixel_size();
+        if x >= width || y >= height {
+            return None;
+        }
+        Some(y * width + x)
+    }
+}
+
+/// Returns the squared distance between two colors, as RGB.
+fn distance(a: Color, b: Color) -> u32 {
+    let (a, b) = (a.to_rgb(), b.to_rgb());
+    [(a.0, b.0), (a.1, b.1), (a.2, b.2)]
+        .into_iter()
+        .map(|(a, b)| u32::from(a.abs_diff(b)).pow(2))
+        .sum()
+}
+
+impl Sprite for BlockCanvas {
+    type Element = char;
+
+    fn width(&self) -> usize {
+        self.width
+    }
+
+    fn height(&self) -> usize {
+        self.height
+    }
+
+    fn draw_to(&self, position: (usize, usize), to: &mut impl GridWriter<Element = Self::Element>) {
+        let (x, y) = position;
+        for row in 0..self.height {
+            for column in 0..self.width {
+                if let Some(c) = self.cell((column, row)) {
+                    to.set((x + column, y + row), c);
+                }
+            }
+        }
+    }
+}
+
+/// A sprite that draws a [`BlockCanvas`] as styled cells.
+///
+/// Created by [`BlockCanvas::styled`].
+#[derive(Clone, Copy, Debug)]
+pub struct StyledBlocks<'a> {
+    canvas: &'a BlockCanvas,
+}
+
+impl Sprite for StyledBlocks<'_> {
+    type Element = Styled;
+
+    fn width(&self) -> usize {
+        self.canvas.width
+    }
+
+    fn height(&self) -> usize {
+        self.canvas.height
+    }
+
+    fn draw_to(&self, position: (usize, usize), to: &mut impl GridWriter<Element = Self::Element>) {
+        let (x, y) = position;
+        for row in 0..self.canvas.height {
+            for column in 0..self.canvas.width {
+                if let Some(cell) = self.canvas.styled_cell((column, row)) {
+                    to.set((x + column, y + row), cell);
+                }
+            }
+        }
+    }
+}
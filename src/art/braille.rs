@@ -0,0 +1,194 @@
+use alloc::{vec, vec::Vec};
+
+use crate::{art::Sprite, GridWriter};
+
+/// The bit of a braille character for each dot, indexed by `[y][x]` within a cell.
+const DOTS: [[u8; 2]; 4] = [[0x01, 0x08], [0x02, 0x10], [0x04, 0x20], [0x40, 0x80]];
+
+/// A canvas of pixels drawn as [braille] characters, i.e. 2×4 pixels per cell.
+///
+/// Pixels are addressed by `(x, y)`, from the top-left corner; a canvas that is `width` cells wide
+/// and `height` cells high has `width * 2` by `height * 4` pixels. Pixels outside the canvas are
+/// ignored, so shapes can be drawn partially off the canvas.
+///
+/// When drawn, each cell is a braille character, or a space if none of its pixels are set.
+///
+/// [braille]: https://en.wikipedia.org/wiki/Braille_Patterns
+///
+/// # Examples
+///
+/// ```
+/// # use grux::art::{BrailleCanvas, Sprite};
+/// let mut canvas = BrailleCanvas::new(2, 1);
+/// canvas.line((0, 0), (3, 3));
+///
+/// let mut grid = [[' '; 2]; 1];
+/// canvas.draw_to((0, 0), &mut grid);
+///
+/// assert_eq!(grid, [['⠑', '⢄']]);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct BrailleCanvas {
+    width: usize,
+    height: usize,
+    cells: Vec<u8>,
+}
+
+impl BrailleCanvas {
+    /// Creates an empty canvas of the given size, in cells.
+    #[must_use]
+    pub fn new(width: usize, height: usize) -> Self {
+        Self {
+            width,
+            height,
+            cells: vec![0; width * height],
+        }
+    }
+
+    /// Returns the `(width, height)` of the canvas, in pixels.
+    #[must_use]
+    pub fn pixel_size(&self) -> (usize, usize) {
+        (self.width * 2, self.height * 4)
+    }
+
+    /// Returns whether the pixel at the given `(x, y)` position is set.
+    ///
+    /// Pixels outside the canvas are never set.
+    #[must_use]
+    pub fn get_pixel(&self, position: (usize, usize)) -> bool {
+        self.index(position)
+            .is_some_and(|(index, bit)| self.cells[index] & bit != 0)
+    }
+
+    /// Sets the pixel at the given `(x, y)` position, if it is inside the canvas.
+    pub fn set_pixel(&mut self, position: (usize, usize)) {
+        if let Some((index, bit)) = self.index(position) {
+            self.cells[index] |= bit;
+        }
+    }
+
+    /// Clears the pixel at the given `(x, y)` position, if it is inside the canvas.
+    pub fn unset_pixel(&mut self, position: (usize, usize)) {
+        if let Some((index, bit)) = self.index(position) {
+            self.cells[index] &= !bit;
+        }
+    }
+
+    /// Clears every pixel.
+    pub fn clear(&mut self) {
+        self.cells.fill(0);
+    }
+
+    /// Sets the pixels of a straight line between two `(x, y)` positions, inclusive.
+    pub fn line(&mut self, from: (usize, usize), to: (usize, usize)) {
+        let from = (from.0 as isize, from.1 as isize);
+        let to = (to.0 as isize, to.1 as isize);
+        for_each_line_point(from, to, |point| self.set_signed_pixel(point));
+    }
+
+    /// Sets the pixels of the outline of a circle with the given center `(x, y)` and radius.
+    pub fn circle(&mut self, center: (usize, usize), radius: usize) {
+        let (cx, cy) = (center.0 as isize, center.1 as isize);
+        let (mut x, mut y) = (radius as isize, 0);
+
+        // The midpoint circle algorithm, mirrored into each octant.
+        let mut error = 1 - x;
+        while x >= y {
+            for (dx, dy) in [(x, y), (y, x), (-y, x), (-x, y)] {
+                self.set_signed_pixel((cx + dx, cy + dy));
+                self.set_signed_pixel((cx - dx, cy - dy));
+            }
+            y += 1;
+            if error < 0 {
+                error += 2 * y + 1;
+            } else {
+                x -= 1;
+                error += 2 * (y - x) + 1;
+            }
+        }
+    }
+
+    /// Returns the braille character of the cell at the given `(x, y)` position, or `None` if it
+    /// is outside the canvas.
+    ///
+    /// A cell without any pixels set is the blank braille character, `'\u{2800}'`.
+    #[must_use]
+    pub fn cell(&self, position: (usize, usize)) -> Option<char> {
+        let (x, y) = position;
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+        char::from_u32(0x2800 + u32::from(self.cells[y * self.width + x]))
+    }
+
+    pub(super) fn set_signed_pixel(&mut self, position: (isize, isize)) {
+        if let (Ok(x), Ok(y)) = (usize::try_from(position.0), usize::try_from(position.1)) {
+            self.set_pixel((x, y));
+        }
+    }
+
+    /// Returns the index of the cell, and the bit of the pixel within it.
+    fn index(&self, position: (usize, usize)) -> Option<(usize, u8)> {
+        let (x, y) = position;
+        let (column, row) = (x / 2, y / 4);
+        if column >= self.width || row >= self.height {
+            return None;
+        }
+        Some((row * self.width + column, DOTS[y % 4][x % 2]))
+    }
+}
+
+/// Calls `f` with each `(x, y)` point of a straight line between two points, inclusive.
+pub(super) fn for_each_line_point(
+    from: (isize, isize),
+    to: (isize, isize),
+    mut f: impl FnMut((isize, isize)),
+) {
+    let (mut x, mut y) = from;
+    let (x1, y1) = to;
+    let (dx, dy) = ((x1 - x).abs(), -(y1 - y).abs());
+    let (sx, sy) = ((x1 - x).signum(), (y1 - y).signum());
+
+    // Bresenham's line algorithm.
+    let mut error = dx + dy;
+    loop {
+        f((x, y));
+        if (x, y) == (x1, y1) {
+            break;
+        }
+        let doubled = 2 * error;
+        if doubled >= dy {
+            error += dy;
+            x += sx;
+        }
+        if doubled <= dx {
+            error += dx;
+            y += sy;
+        }
+    }
+}
+
+impl Sprite for BrailleCanvas {
+    type Element = char;
+
+    fn width(&self) -> usize {
+        self.width
+    }
+
+    fn height(&self) -> usize {
+        self.height
+    }
+
+    fn draw_to(&self, position: (usize, usize), to: &mut impl GridWriter<Element = Self::Element>) {
+        let (x, y) = position;
+        for row in 0..self.height {
+            for column in 0..self.width {
+                let c = match self.cells[row * self.width + column] {
+                    0 => ' ',
+                    _ => self.cell((column, row)).unwrap_or(' '),
+                };
+                to.set((x + column, y + row), c);
+            }
+        }
+    }
+}
@@ -0,0 +1,379 @@
+use alloc::{string::String, vec::Vec};
+
+use crate::{
+    art::{Orientation, Sprite},
+    GridWriter,
+};
+
+/// Blocks that fill the bottom eighths of a cell, from one eighth to the full cell.
+const VERTICAL_EIGHTHS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// Blocks that fill the left eighths of a cell, from one eighth to the full cell.
+pub(super) const HORIZONTAL_EIGHTHS: [char; 8] = ['▏', '▎', '▍', '▌', '▋', '▊', '▉', '█'];
+
+/// Returns the block for a number of eighths of a cell, or a space if it is empty.
+pub(super) fn eighths(blocks: &[char; 8], eighths: usize) -> char {
+    match eighths {
+        0 => ' ',
+        n => blocks[n.min(8) - 1],
+    }
+}
+
+/// Scales a value in `0.0..=1.0` to a number of steps in `0..=steps`, rounding to the nearest.
+///
+/// Values outside the range (and `NaN`) are clamped.
+pub(super) fn scale(fraction: f64, steps: usize) -> usize {
+    ((fraction * steps as f64 + 0.5) as usize).min(steps)
+}
+
+/// A small line graph of values, drawn with the blocks `▁▂▃▄▅▆▇█`.
+///
+/// The values are scaled to the size of the sprite: each column is the average of the values that
+/// fall into it, and the smallest value is drawn as `▁` and the largest as a full column. A
+/// sparkline is one cell high by default, with one column per value.
+///
+/// # Examples
+///
+/// ```
+/// # use grux::art::{Sparkline, Sprite};
+/// let sparkline = Sparkline::new([1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 4.0]);
+///
+/// let mut grid = [[' '; 9]; 1];
+/// sparkline.draw_to((0, 0), &mut grid);
+///
+/// assert_eq!(String::from_iter(grid[0]), "▁▂▃▄▅▆▇█▄");
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Sparkline {
+    data: Vec<f64>,
+    width: usize,
+    height: usize,
+    range: Option<(f64, f64)>,
+}
+
+impl Sparkline {
+    /// Configures a sparkline of the given values, one cell high and one column per value.
+    #[must_use]
+    pub fn new(data: impl Into<Vec<f64>>) -> Self {
+        let data = data.into();
+        Self {
+            width: data.len(),
+            height: 1,
+            data,
+            range: None,
+        }
+    }
+
+    /// Sets the size of the sparkline, in cells; the values are averaged or stretched to fit.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use grux::art::{Sparkline, Sprite};
+    /// let sparkline = Sparkline::new([0.0, 2.0, 4.0, 6.0]).with_size(2, 2);
+    ///
+    /// let mut grid = [[' '; 2]; 2];
+    /// sparkline.draw_to((0, 0), &mut grid);
+    ///
+    /// assert_eq!(grid, [[' ', '▆'], ['▄', '█']]);
+    /// ```
+    #[must_use]
+    pub fn with_size(mut self, width: usize, height: usize) -> Self {
+        self.width = width;
+        self.height = height;
+        self
+    }
+
+    /// Sets the `(min, max)` range of the values, instead of the smallest and largest values.
+    ///
+    /// Values outside the range are clamped.
+    #[must_use]
+    pub fn with_range(mut self, min: f64, max: f64) -> Self {
+        self.range = Some((min, max));
+        self
+    }
+
+    /// Returns the values of the sparkline.
+    #[must_use]
+    pub fn data(&self) -> &[f64] {
+        &self.data
+    }
+
+    /// Returns the `(min, max)` range that values are scaled from.
+    fn range(&self) -> (f64, f64) {
+        self.range.unwrap_or_else(|| {
+            let values = self.data.iter().copied().filter(|value| !value.is_nan());
+            let min = values.clone().fold(f64::INFINITY, f64::min);
+            let max = values.fold(f64::NEG_INFINITY, f64::max);
+            (min, max)
+        })
+    }
+
+    /// Returns the average of the values in a column, or `None` if there are none.
+    fn column(&self, column: usize) -> Option<f64> {
+        let len = self.data.len();
+        let start = column * len / self.width;
+        let end = ((column + 1) * len / self.width).max(start + 1).min(len);
+        let values = self.data.get(start..end)?;
+        let values = values.iter().filter(|value| !value.is_nan());
+        let count = values.clone().count();
+        (count > 0).then(|| values.sum::<f64>() / count as f64)
+    }
+}
+
+impl Sprite for Sparkline {
+    type Element = char;
+
+    fn width(&self) -> usize {
+        self.width
+    }
+
+    fn height(&self) -> usize {
+        self.height
+    }
+
+    fn draw_to(&self, position: (usize, usize), to: &mut impl GridWriter<Element = Self::Element>) {
+        let (x, y) = position;
+        let (min, max) = self.range();
+        let steps = (self.height * 8).saturating_sub(1);
+
+        for column in 0..self.width {
+            // The height of the column in eighths of a cell; any value is at least one eighth.
+            let level = self.column(column).map_or(0, |value| {
+                let fraction = if max > min {
+                    (value - min) / (max - min)
+                } else {
+                    0.0
+                };
+                1 + scale(fraction, steps)
+            });
+            for row in 0..self.height {
+                let filled = level.saturating_sub((self.height - 1 - row) * 8);
+                to.set((x + column, y + row), eighths(&VERTICAL_EIGHTHS, filled));
+            }
+        }
+    }
+}
+
+/// A chart of labelled bars, drawn with eighth blocks so that bars can end partway into a cell.
+///
+/// The bars are scaled to the size of the sprite, from zero to the largest value (or the maximum
+/// set with [`BarChart::with_max`]); negative values are drawn as empty bars.
+///
+/// - [`Orientation::Vertical`] bars grow upwards, with their labels on the bottom row (if any bar
+///   has a label), truncated to the width of the bar.
+/// - [`Orientation::Horizontal`] bars grow to the right, with their labels on the left, padded to
+///   the longest label.
+///
+/// Bars that do not fit in the chart are not drawn.
+///
+/// # Examples
+///
+/// ```
+/// # use grux::art::{BarChart, Orientation, Sprite};
+/// let chart = BarChart::new(Orientation::Horizontal, 6, 3)
+///     .with_bar("cpu", 1.0)
+///     .with_bar("io", 0.25)
+///     .with_bar("net", 0.3);
+///
+/// let mut grid = [[' '; 6]; 3];
+/// chart.draw_to((0, 0), &mut grid);
+///
+/// assert_eq!(grid, [
+///     ['c', 'p', 'u', ' ', '█', '█'],
+///     ['i', 'o', ' ', ' ', '▌', ' '],
+///     ['n', 'e', 't', ' ', '▋', ' '],
+/// ]);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BarChart {
+    orientation: Orientation,
+    width: usize,
+    height: usize,
+    bars: Vec<(String, f64)>,
+    max: Option<f64>,
+    bar_width: usize,
+    gap: usize,
+}
+
+impl BarChart {
+    /// Configures an empty bar chart of the given orientation and size, in cells.
+    ///
+    /// Bars are one cell thick; vertical bars have one cell between them, and horizontal bars
+    /// are on consecutive rows.
+    #[must_use]
+    pub fn new(orientation: Orientation, width: usize, height: usize) -> Self {
+        Self {
+            orientation,
+            width,
+            height,
+            bars: Vec::new(),
+            max: None,
+            bar_width: 1,
+            gap: match orientation {
+                Orientation::Horizontal => 0,
+                Orientation::Vertical => 1,
+            },
+        }
+    }
+
+    /// Adds a bar with the given label and value.
+    #[must_use]
+    pub fn with_bar(mut self, label: impl Into<String>, value: f64) -> Self {
+        self.bars.push((label.into(), value));
+        self
+    }
+
+    /// Sets the value of a full bar, instead of the largest value.
+    #[must_use]
+    pub fn with_max(mut self, max: f64) -> Self {
+        self.max = Some(max);
+        self
+    }
+
+    /// Sets how many cells thick each bar is, and how many cells are between bars.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use grux::art::{BarChart, Orientation, Sprite};
+    /// let chart = BarChart::new(Orientation::Vertical, 5, 3)
+    ///     .with_bar("a", 4.0)
+    ///     .with_bar("bc", 1.0)
+    ///     .with_bar_width(2, 1);
+    ///
+    /// let mut grid = [['.'; 5]; 3];
+    /// chart.draw_to((0, 0), &mut grid);
+    ///
+    /// assert_eq!(grid, [
+    ///     ['█', '█', ' ', ' ', ' '],
+    ///     ['█', '█', ' ', '▄', '▄'],
+    ///     ['a', ' ', ' ', 'b', 'c'],
+    /// ]);
+    /// ```
+    #[must_use]
+    pub fn with_bar_width(mut self, bar_width: usize, gap: usize) -> Self {
+        self.bar_width = bar_width;
+        self.gap = gap;
+        self
+    }
+
+    /// Returns the orientation of the bars.
+    #[must_use]
+    pub fn orientation(&self) -> Orientation {
+        self.orientation
+    }
+
+    /// Returns the labels and values of the bars.
+    #[must_use]
+    pub fn bars(&self) -> &[(String, f64)] {
+        &self.bars
+    }
+
+    /// Returns the length of a bar in eighths of a cell, out of the given number of cells.
+    fn length(&self, value: f64, cells: usize) -> usize {
+        let max = self.max.unwrap_or_else(|| {
+            self.bars
+                .iter()
+                .map(|(_, value)| *value)
+                .fold(0.0, f64::max)
+        });
+        if max > 0.0 {
+            scale(value / max, cells * 8)
+        } else {
+            0
+        }
+    }
+
+    /// Returns the `(offset, label)` of each bar that fits within the given number of cells.
+    fn visible_bars(&self, cells: usize) -> impl Iterator<Item = (usize, &(String, f64))> + '_ {
+        let stride = self.bar_width + self.gap;
+        self.bars
+            .iter()
+            .enumerate()
+            .map(move |(i, bar)| (i * stride, bar))
+            .take_while(move |(offset, _)| offset + self.bar_width <= cells)
+    }
+
+    fn draw_vertical(&self, (x, y): (usize, usize), to: &mut impl GridWriter<Element = char>) {
+        let has_labels = self.bars.iter().any(|(label, _)| !label.is_empty());
+        let rows = self.height.saturating_sub(usize::from(has_labels));
+        for row in 0..self.height {
+            for column in 0..self.width {
+                to.set((x + column, y + row), ' ');
+            }
+        }
+
+        for (offset, (label, value)) in self.visible_bars(self.width) {
+            let length = self.length(*value, rows);
+            for row in 0..rows {
+                let filled = length.saturating_sub((rows - 1 - row) * 8);
+                for i in 0..self.bar_width {
+                    let c = eighths(&VERTICAL_EIGHTHS, filled);
+                    to.set((x + offset + i, y + row), c);
+                }
+            }
+            if has_labels && rows < self.height {
+                for (i, c) in label.chars().take(self.bar_width).enumerate() {
+                    to.set((x + offset + i, y + rows), c);
+                }
+            }
+        }
+    }
+
+    fn draw_horizontal(&self, (x, y): (usize, usize), to: &mut impl GridWriter<Element = char>) {
+        let label_width = match self
+            .bars
+            .iter()
+            .map(|(label, _)| label.chars().count())
+            .max()
+        {
+            Some(0) | None => 0,
+            Some(longest) => (longest + 1).min(self.width),
+        };
+        let columns = self.width - label_width;
+        for row in 0..self.height {
+            for column in 0..self.width {
+                to.set((x + column, y + row), ' ');
+            }
+        }
+
+        for (offset, (label, value)) in self.visible_bars(self.height) {
+            let length = self.length(*value, columns);
+            for i in 0..self.bar_width {
+                let row = y + offset + i;
+                if i == 0 {
+                    for (column, c) in label.chars().take(label_width).enumerate() {
+                        to.set((x + column, row), c);
+                    }
+                }
+                for column in 0..columns {
+                    let filled = length.saturating_sub(column * 8);
+                    let c = eighths(&HORIZONTAL_EIGHTHS, filled);
+                    to.set((x + label_width + column, row), c);
+                }
+            }
+        }
+    }
+}
+
+impl Sprite for BarChart {
+    type Element = char;
+
+    fn width(&self) -> usize {
+        self.width
+    }
+
+    fn height(&self) -> usize {
+        self.height
+    }
+
+    fn draw_to(&self, position: (usize, usize), to: &mut impl GridWriter<Element = Self::Element>) {
+        match self.orientation {
+            Orientation::Vertical => self.draw_vertical(position, to),
+            Orientation::Horizontal => self.draw_horizontal(position, to),
+        }
+    }
+}
@@ -0,0 +1,341 @@
+use alloc::{boxed::Box, vec::Vec};
+use core::fmt::{self, Display};
+
+use crate::{adapter::Viewport, art::Sprite, GridWriter, Rect};
+
+/// An object-safe version of [`Sprite`], so that sprites of different types can be children of
+/// the same [`Flex`].
+trait DynSprite<T> {
+    fn size(&self) -> (usize, usize);
+
+    fn draw_dyn(&self, position: (usize, usize), to: &mut dyn GridWriter<Element = T>);
+}
+
+impl<S: Sprite> DynSprite<S::Element> for S
+where
+    S::Element: Display,
+{
+    fn size(&self) -> (usize, usize) {
+        (self.width(), self.height())
+    }
+
+    fn draw_dyn(
+        &self,
+        position: (usize, usize),
+        mut to: &mut dyn GridWriter<Element = S::Element>,
+    ) {
+        self.draw_to(position, &mut to);
+    }
+}
+
+/// How the children of a [`Flex`] are arranged.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+enum Container {
+    Row,
+    Column,
+    Stack,
+}
+
+/// Where children are placed across a [`Flex`], e.g. vertically in a row.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum FlexAlign {
+    /// At the top of a row, or the left of a column.
+    #[default]
+    Start,
+
+    /// In the middle, rounded towards the start.
+    Center,
+
+    /// At the bottom of a row, or the right of a column.
+    End,
+}
+
+/// How the space left over along a [`Flex`] is distributed, e.g. horizontally in a row.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum FlexJustify {
+    /// After the children.
+    #[default]
+    Start,
+
+    /// Evenly before and after the children, rounded towards the start.
+    Center,
+
+    /// Before the children.
+    End,
+
+    /// Between the children, but not before the first or after the last.
+    SpaceBetween,
+
+    /// Between the children, and before the first and after the last.
+    SpaceEvenly,
+}
+
+/// A container of sprites, arranged in a row, a column, or stacked on top of each other.
+///
+/// Each child is measured with [`Sprite::width`] and [`Sprite::height`] and drawn at its computed
+/// position; a `Flex` is itself a sprite, so containers can be nested to build a screen. Unless set
+/// with [`Flex::with_size`], a container is as large as its children (and the gaps between them),
+/// and anything drawn outside of its size is clipped.
+///
+/// - [`Flex::row`] places children side by side, left to right.
+/// - [`Flex::column`] places children top to bottom.
+/// - [`Flex::stack`] places children on top of each other, with the last child drawn on top.
+///
+/// # Examples
+///
+/// ```
+/// # #[cfg(feature = "std")]
+/// # {
+/// # use grux::DisplayGrid;
+/// # use grux::art::{BorderRect, Flex, FlexAlign, FillRect, Line, Sprite};
+/// let header = Line::horizontal(7, '=');
+/// let body = Flex::row()
+///     .with_child(BorderRect::new(3, 3, ['+', '-', '+', '|', '|', '+', '-', '+']))
+///     .with_child(FillRect::new(2, 1, '#'))
+///     .with_gap(1)
+///     .with_align(FlexAlign::Center);
+///
+/// let screen = Flex::column().with_child(header).with_child(body);
+/// assert_eq!((screen.width(), screen.height()), (7, 4));
+///
+/// let mut grid = [['.'; 7]; 4];
+/// screen.draw_to((0, 0), &mut grid);
+///
+/// assert_eq!(grid.to_string().unwrap(), concat!(
+///     "=======\n",
+///     "+-+....\n",
+///     "|.|.##.\n",
+///     "+-+....\n",
+/// ));
+/// # }
+/// ```
+pub struct Flex<'a, T> {
+    container: Container,
+    children: Vec<Box<dyn DynSprite<T> + 'a>>,
+    gap: usize,
+    align: FlexAlign,
+    justify: FlexJustify,
+    size: Option<(usize, usize)>,
+}
+
+impl<'a, T> Flex<'a, T> {
+    fn new(container: Container) -> Self {
+        Self {
+            container,
+            children: Vec::new(),
+            gap: 0,
+            align: FlexAlign::default(),
+            justify: FlexJustify::default(),
+            size: None,
+        }
+    }
+
+    /// Configures an empty container that places its children side by side, left to right.
+    #[must_use]
+    pub fn row() -> Self {
+        Self::new(Container::Row)
+    }
+
+    /// Configures an empty container that places its children top to bottom.
+    #[must_use]
+    pub fn column() -> Self {
+        Self::new(Container::Column)
+    }
+
+    /// Configures an empty container that places its children on top of each other.
+    ///
+    /// Children are aligned on both axes, e.g. [`FlexAlign::Center`] centers every child in the
+    /// container; justification and gaps do not apply.
+    #[must_use]
+    pub fn stack() -> Self {
+        Self::new(Container::Stack)
+    }
+
+    /// Adds a child, e.g. a built-in sprite or another container.
+    #[must_use]
+    pub fn with_child(mut self, child: impl Sprite<Element = T> + 'a) -> Self
+    where
+        T: Display,
+    {
+        self.children.push(Box::new(child));
+        self
+    }
+
+    /// Sets the number of cells between children.
+    #[must_use]
+    pub fn with_gap(mut self, gap: usize) -> Self {
+        self.gap = gap;
+        self
+    }
+
+    /// Sets where children are placed across the container.
+    #[must_use]
+    pub fn with_align(mut self, align: FlexAlign) -> Self {
+        self.align = align;
+        self
+    }
+
+    /// Sets how the space left over along the container is distributed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use grux::art::{FillRect, Flex, FlexJustify, Sprite};
+    /// let row = Flex::row()
+    ///     .with_child(FillRect::new(1, 1, 'a'))
+    ///     .with_child(FillRect::new(1, 1, 'b'))
+    ///     .with_justify(FlexJustify::SpaceBetween)
+    ///     .with_size(5, 1);
+    ///
+    /// let mut grid = [['.'; 5]; 1];
+    /// row.draw_to((0, 0), &mut grid);
+    ///
+    /// assert_eq!(grid, [['a', '.', '.', '.', 'b']]);
+    /// ```
+    #[must_use]
+    pub fn with_justify(mut self, justify: FlexJustify) -> Self {
+        self.justify = justify;
+        self
+    }
+
+    /// Sets the size of the container, instead of fitting its children.
+    #[must_use]
+    pub fn with_size(mut self, width: usize, height: usize) -> Self {
+        self.size = Some((width, height));
+        self
+    }
+
+    /// Returns the number of children.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.children.len()
+    }
+
+    /// Returns `true` if the container has no children.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.children.is_empty()
+    }
+
+    /// Returns the `(main, cross)` axis of a size, i.e. `(width, height)` for a row.
+    fn axes(&self, (width, height): (usize, usize)) -> (usize, usize) {
+        match self.container {
+            Container::Column => (height, width),
+            Container::Row | Container::Stack => (width, height),
+        }
+    }
+
+    /// Returns the size that fits every child.
+    fn natural_size(&self) -> (usize, usize) {
+        let sizes = self.children.iter().map(|child| child.size());
+        if self.container == Container::Stack {
+            return sizes.fold((0, 0), |(w, h), (cw, ch)| (w.max(cw), h.max(ch)));
+        }
+        let (main, cross) = sizes.fold((0, 0), |(main, cross), size| {
+            let (child_main, child_cross) = self.axes(size);
+            (main + child_main, cross.max(child_cross))
+        });
+        let gaps = self.gap * self.children.len().saturating_sub(1);
+        self.axes((main + gaps, cross))
+    }
+
+    /// Returns the offset of a child of the given length, in a space of the given length.
+    fn align(&self, length: usize, space: usize) -> usize {
+        let free = space.saturating_sub(length);
+        match self.align {
+            FlexAlign::Start => 0,
+            FlexAlign::Center => free / 2,
+            FlexAlign::End => free,
+        }
+    }
+
+    /// Returns the size of the container, as set or fitting its children.
+    fn size(&self) -> (usize, usize) {
+        self.size.unwrap_or_else(|| self.natural_size())
+    }
+
+    /// Returns the `(x, y)` offset of each child, within the container.
+    fn positions(&self) -> Vec<(usize, usize)> {
+        let size = self.size();
+        if self.container == Container::Stack {
+            return self
+                .children
+                .iter()
+                .map(|child| {
+                    let (width, height) = child.size();
+                    (self.align(width, size.0), self.align(height, size.1))
+                })
+                .collect();
+        }
+
+        let (main, cross) = self.axes(size);
+        let (content, _) = self.axes(self.natural_size());
+        let free = main.saturating_sub(content);
+        let count = self.children.len();
+
+        // The space before the first child, and between each child (on top of the gap), with any
+        // cells left over from rounding down going to the first spaces.
+        let (mut start, spaces, mut left_over) = match self.justify {
+            FlexJustify::Start => (0, 0, 0),
+            FlexJustify::Center => (free / 2, 0, 0),
+            FlexJustify::End => (free, 0, 0),
+            FlexJustify::SpaceBetween if count > 1 => (0, free / (count - 1), free % (count - 1)),
+            FlexJustify::SpaceBetween => (0, 0, 0),
+            FlexJustify::SpaceEvenly => {
+                let space = free / (count + 1);
+                let left_over = free % (count + 1);
+                (
+                    space + usize::from(left_over > 0),
+                    space,
+                    left_over.saturating_sub(1),
+                )
+            }
+        };
+
+        let mut positions = Vec::with_capacity(count);
+        for child in &self.children {
+            let (child_main, child_cross) = self.axes(child.size());
+            let offset = (start, self.align(child_cross, cross));
+            positions.push(self.axes(offset));
+            start += child_main + self.gap + spaces + usize::from(left_over > 0);
+            left_over = left_over.saturating_sub(1);
+        }
+        positions
+    }
+}
+
+impl<T> fmt::Debug for Flex<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Flex")
+            .field("container", &self.container)
+            .field("children", &self.children.len())
+            .field("gap", &self.gap)
+            .field("align", &self.align)
+            .field("justify", &self.justify)
+            .field("size", &self.size)
+            .finish()
+    }
+}
+
+impl<T: Display + Clone> Sprite for Flex<'_, T> {
+    type Element = T;
+
+    fn width(&self) -> usize {
+        self.size().0
+    }
+
+    fn height(&self) -> usize {
+        self.size().1
+    }
+
+    fn draw_to(&self, position: (usize, usize), to: &mut impl GridWriter<Element = Self::Element>) {
+        let (x, y) = position;
+        let rect = Rect::new(x, y, self.width(), self.height());
+        let mut viewport = Viewport::new(to, rect);
+        for (child, position) in self.children.iter().zip(self.positions()) {
+            child.draw_dyn(position, &mut viewport);
+        }
+    }
+}
@@ -0,0 +1,277 @@
+use alloc::{string::String, vec::Vec};
+
+use crate::{
+    art::{table::align, Alignment, Sprite},
+    style::{Style, Styled},
+    GridWriter,
+};
+
+/// The selection and scroll position of a [`List`], kept by the application between frames.
+///
+/// Input is handled by changing the state, e.g. with [`ListState::select_next`] on a key press;
+/// the list then draws the items that are scrolled into view, with the selected item highlighted.
+///
+/// # Examples
+///
+/// ```
+/// # use grux::art::ListState;
+/// let mut state = ListState::new();
+/// assert_eq!(state.selected(), None);
+///
+/// // Select the last of 5 items, in a list with room for 3.
+/// for _ in 0..5 {
+///     state.select_next(5);
+/// }
+/// state.scroll_into_view(5, 3);
+///
+/// assert_eq!(state.selected(), Some(4));
+/// assert_eq!(state.offset(), 2);
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ListState {
+    selected: Option<usize>,
+    offset: usize,
+}
+
+impl ListState {
+    /// Creates a state with no selection, scrolled to the top.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            selected: None,
+            offset: 0,
+        }
+    }
+
+    /// Sets the selected item.
+    #[must_use]
+    pub const fn with_selected(mut self, selected: Option<usize>) -> Self {
+        self.selected = selected;
+        self
+    }
+
+    /// Returns the index of the selected item, if any.
+    #[must_use]
+    pub const fn selected(&self) -> Option<usize> {
+        self.selected
+    }
+
+    /// Selects an item by index, or clears the selection.
+    pub fn select(&mut self, selected: Option<usize>) {
+        self.selected = selected;
+    }
+
+    /// Returns the index of the first item in view.
+    #[must_use]
+    pub const fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Sets the index of the first item in view.
+    pub fn set_offset(&mut self, offset: usize) {
+        self.offset = offset;
+    }
+
+    /// Selects the item after the selected one, out of `len` items, or the first if none is.
+    ///
+    /// The selection stays on the last item; with no items, the selection is cleared.
+    pub fn select_next(&mut self, len: usize) {
+        self.selected = match self.selected {
+            _ if len == 0 => None,
+            None => Some(0),
+            Some(selected) => Some((selected + 1).min(len - 1)),
+        };
+    }
+
+    /// Selects the item before the selected one, out of `len` items, or the last if none is.
+    ///
+    /// The selection stays on the first item; with no items, the selection is cleared.
+    pub fn select_previous(&mut self, len: usize) {
+        self.selected = match self.selected {
+            _ if len == 0 => None,
+            None => Some(len - 1),
+            Some(selected) => Some(selected.saturating_sub(1).min(len - 1)),
+        };
+    }
+
+    /// Scrolls as little as possible so that the selected item is in view, out of `len` items
+    /// with room for `height` of them.
+    ///
+    /// A selection past the last item is moved to the last item, and the list is not scrolled
+    /// past the point where the last item is at the bottom.
+    pub fn scroll_into_view(&mut self, len: usize, height: usize) {
+        self.selected = self.selected.filter(|_| len > 0).map(|i| i.min(len - 1));
+        self.offset = self.offset.min(len.saturating_sub(height));
+        if let Some(selected) = self.selected {
+            if selected < self.offset {
+                self.offset = selected;
+            } else if height > 0 && selected >= self.offset + height {
+                self.offset = selected + 1 - height;
+            }
+        }
+    }
+}
+
+/// A scrollable list of text items, one per row, with a highlighted selected item.
+///
+/// Which item is selected, and which items are in view, is kept in a [`ListState`]. When drawn,
+/// the list scrolls (without changing its state) so that the selected item is in view; to keep
+/// the scroll position between frames, call [`ListState::scroll_into_view`] after changing the
+/// selection.
+///
+/// The selected row is drawn with the highlight style (by default, reversed), and each row can be
+/// prefixed with a marker, e.g. `>` for the selected item. Items that are wider than the list are
+/// truncated with `…`.
+///
+/// # Examples
+///
+/// ```
+/// # use grux::art::{List, ListState, Sprite};
+/// # use grux::style::{Style, Styled};
+/// let list = List::new(8, 2, ["apple", "banana", "cherry"])
+///     .with_markers("> ", "  ")
+///     .with_state(ListState::new().with_selected(Some(2)));
+///
+/// let mut grid = [[Styled::from('.'); 8]; 2];
+/// list.draw_to((0, 0), &mut grid);
+///
+/// let rows = grid.map(|row| String::from_iter(row.map(|cell| cell.value)));
+/// assert_eq!(rows, ["  banana", "> cherry"]);
+/// assert_eq!(grid[0][0].style, Style::new());
+/// assert_eq!(grid[1][0].style, Style::new().reverse());
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct List {
+    width: usize,
+    height: usize,
+    items: Vec<String>,
+    state: ListState,
+    markers: (String, String),
+    highlight: Style,
+    alignment: Alignment,
+}
+
+impl List {
+    /// Configures a list of the given size, in cells, with one item per row.
+    #[must_use]
+    pub fn new(
+        width: usize,
+        height: usize,
+        items: impl IntoIterator<Item = impl Into<String>>,
+    ) -> Self {
+        Self {
+            width,
+            height,
+            items: items.into_iter().map(Into::into).collect(),
+            state: ListState::new(),
+            markers: (String::new(), String::new()),
+            highlight: Style::new().reverse(),
+            alignment: Alignment::default(),
+        }
+    }
+
+    /// Sets the selection and scroll position.
+    #[must_use]
+    pub fn with_state(mut self, state: ListState) -> Self {
+        self.state = state;
+        self
+    }
+
+    /// Sets the markers drawn before the selected item, and before every other item.
+    ///
+    /// The shorter marker is padded with spaces, so that the items line up.
+    #[must_use]
+    pub fn with_markers(mut self, selected: impl Into<String>, other: impl Into<String>) -> Self {
+        self.markers = (selected.into(), other.into());
+        self
+    }
+
+    /// Sets the style of the selected row; defaults to reversed colors.
+    #[must_use]
+    pub fn with_highlight(mut self, style: Style) -> Self {
+        self.highlight = style;
+        self
+    }
+
+    /// Sets how items are aligned after the marker, e.g. centered for a menu.
+    #[must_use]
+    pub fn with_alignment(mut self, alignment: Alignment) -> Self {
+        self.alignment = alignment;
+        self
+    }
+
+    /// Returns the items of the list.
+    #[must_use]
+    pub fn items(&self) -> &[String] {
+        &self.items
+    }
+
+    /// Returns the selection and scroll position.
+    #[must_use]
+    pub fn state(&self) -> &ListState {
+        &self.state
+    }
+
+    /// Returns the selection and scroll position, to change it between frames.
+    pub fn state_mut(&mut self) -> &mut ListState {
+        &mut self.state
+    }
+}
+
+impl Sprite for List {
+    type Element = Styled;
+
+    fn width(&self) -> usize {
+        self.width
+    }
+
+    fn height(&self) -> usize {
+        self.height
+    }
+
+    fn draw_to(&self, position: (usize, usize), to: &mut impl GridWriter<Element = Self::Element>) {
+        let (x, y) = position;
+        let mut state = self.state;
+        state.scroll_into_view(self.items.len(), self.height);
+
+        let (selected_marker, other_marker) = &self.markers;
+        let marker_width = selected_marker
+            .chars()
+            .count()
+            .max(other_marker.chars().count())
+            .min(self.width);
+        let text_width = self.width - marker_width;
+
+        for row in 0..self.height {
+            let index = state.offset + row;
+            let is_selected = state.selected == Some(index);
+            let style = if is_selected {
+                self.highlight
+            } else {
+                Style::new()
+            };
+            for column in 0..self.width {
+                to.set((x + column, y + row), Styled::new(' ', style));
+            }
+            let Some(item) = self.items.get(index) else {
+                continue;
+            };
+
+            let marker = if is_selected {
+                selected_marker
+            } else {
+                other_marker
+            };
+            for (column, c) in marker.chars().take(marker_width).enumerate() {
+                to.set((x + column, y + row), Styled::new(c, style));
+            }
+            let (offset, chars) = align(item, text_width, self.alignment);
+            for (column, c) in chars.enumerate() {
+                let column = marker_width + offset + column;
+                to.set((x + column, y + row), Styled::new(c, style));
+            }
+        }
+    }
+}
@@ -0,0 +1,483 @@
+use alloc::{format, string::String, vec::Vec};
+
+use crate::{
+    art::{
+        braille::{for_each_line_point, BrailleCanvas},
+        Line, Sprite,
+    },
+    GridWriter,
+};
+
+/// How the points of a [`Series`] are drawn.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Marker {
+    /// A character per point, e.g. `*`, at the resolution of cells.
+    Char(char),
+
+    /// Braille dots, at 2×4 times the resolution of cells; see
+    /// [`BrailleCanvas`][`crate::art::BrailleCanvas`].
+    Braille,
+}
+
+impl Default for Marker {
+    fn default() -> Self {
+        Marker::Char('*')
+    }
+}
+
+impl Marker {
+    /// Returns the character that represents the marker in a legend.
+    const fn legend(self) -> char {
+        match self {
+            Marker::Char(c) => c,
+            Marker::Braille => '⣿',
+        }
+    }
+}
+
+/// A labelled series of `(x, y)` points in a [`Plot`].
+///
+/// By default, the points are drawn as `*` and are not connected, i.e. as a scatter plot.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Series {
+    label: String,
+    points: Vec<(f64, f64)>,
+    marker: Marker,
+    lines: bool,
+}
+
+impl Series {
+    /// Configures a series with the given label and points.
+    ///
+    /// Series with an empty label are not shown in the legend.
+    #[must_use]
+    pub fn new(label: impl Into<String>, points: impl Into<Vec<(f64, f64)>>) -> Self {
+        Self {
+            label: label.into(),
+            points: points.into(),
+            marker: Marker::default(),
+            lines: false,
+        }
+    }
+
+    /// Sets how the points are drawn.
+    #[must_use]
+    pub fn with_marker(mut self, marker: Marker) -> Self {
+        self.marker = marker;
+        self
+    }
+
+    /// Sets whether consecutive points are connected by lines, i.e. a line plot.
+    #[must_use]
+    pub fn with_lines(mut self, lines: bool) -> Self {
+        self.lines = lines;
+        self
+    }
+
+    /// Returns the label of the series.
+    #[must_use]
+    pub fn label(&self) -> &str {
+        &self.label
+    }
+
+    /// Returns the points of the series.
+    #[must_use]
+    pub fn points(&self) -> &[(f64, f64)] {
+        &self.points
+    }
+
+    /// Calls `f` with each `(column, row)` cell (or pixel) of an area of the given size that the
+    /// series covers, when the area spans the given `(x, y)` ranges.
+    ///
+    /// Points outside the ranges are skipped, and lines are clipped to the ranges first, so only
+    /// cells inside the area are visited.
+    fn for_each_point(
+        &self,
+        ranges: ((f64, f64), (f64, f64)),
+        (width, height): (usize, usize),
+        mut f: impl FnMut((isize, isize)),
+    ) {
+        let ((x_min, x_max), (y_min, y_max)) = ranges;
+        let scale = |(px, py): (f64, f64)| {
+            let fx = (px - x_min) / (x_max - x_min);
+            let fy = (py - y_min) / (y_max - y_min);
+            (
+                round(fx * (width - 1) as f64),
+                ((height - 1) as isize).saturating_sub(round(fy * (height - 1) as f64)),
+            )
+        };
+        let points: Vec<_> = self
+            .points
+            .iter()
+            .copied()
+            .filter(|(x, y)| x.is_finite() && y.is_finite())
+            .collect();
+
+        if self.lines && points.len() > 1 {
+            for pair in points.windows(2) {
+                if let Some((from, to)) = clip(pair[0], pair[1], ranges) {
+                    for_each_line_point(scale(from), scale(to), &mut f);
+                }
+            }
+        } else {
+            for &(x, y) in &points {
+                if (x_min..=x_max).contains(&x) && (y_min..=y_max).contains(&y) {
+                    f(scale((x, y)));
+                }
+            }
+        }
+    }
+}
+
+/// A line or scatter plot of one or more [`Series`], with axes, tick labels and a legend.
+///
+/// The plot fills its size: tick labels for the Y axis are on the left, the X axis and its tick
+/// labels are on the bottom two rows, and the legend (if any series has a label) is in the top
+/// right corner. Unless set, the ranges of the axes fit every point.
+///
+/// # Examples
+///
+/// ```
+/// # #[cfg(feature = "std")]
+/// # {
+/// # use grux::DisplayGrid;
+/// # use grux::art::{Plot, Series, Sprite};
+/// let plot = Plot::new(12, 5)
+///     .with_series(Series::new("", [(0.0, 0.0), (1.0, 1.0), (2.0, 4.0), (3.0, 9.0)]))
+///     .with_ticks(2, 2);
+///
+/// let mut grid = [[' '; 12]; 5];
+/// plot.draw_to((0, 0), &mut grid);
+///
+/// assert_eq!(grid.to_string().unwrap(), concat!(
+///     "9┤         *\n",
+///     " │      *   \n",
+///     "0┤*  *      \n",
+///     " └┬────────┬\n",
+///     "  0        3\n",
+/// ));
+/// # }
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Plot {
+    width: usize,
+    height: usize,
+    series: Vec<Series>,
+    x_range: Option<(f64, f64)>,
+    y_range: Option<(f64, f64)>,
+    ticks: (usize, usize),
+    legend: bool,
+}
+
+impl Plot {
+    /// Configures an empty plot of the given size, in cells.
+    ///
+    /// Each axis has 3 ticks, i.e. at both ends and in the middle.
+    #[must_use]
+    pub fn new(width: usize, height: usize) -> Self {
+        Self {
+            width,
+            height,
+            series: Vec::new(),
+            x_range: None,
+            y_range: None,
+            ticks: (3, 3),
+            legend: true,
+        }
+    }
+
+    /// Adds a series; series are drawn in the order they are added.
+    #[must_use]
+    pub fn with_series(mut self, series: Series) -> Self {
+        self.series.push(series);
+        self
+    }
+
+    /// Sets the `(min, max)` range of the X axis, instead of fitting every point.
+    ///
+    /// Points outside the range are not drawn, and lines to them are clipped at the edge.
+    #[must_use]
+    pub fn with_x_range(mut self, min: f64, max: f64) -> Self {
+        self.x_range = Some((min, max));
+        self
+    }
+
+    /// Sets the `(min, max)` range of the Y axis, instead of fitting every point.
+    ///
+    /// Points outside the range are not drawn, and lines to them are clipped at the edge.
+    #[must_use]
+    pub fn with_y_range(mut self, min: f64, max: f64) -> Self {
+        self.y_range = Some((min, max));
+        self
+    }
+
+    /// Sets the number of ticks on the X and Y axes, including both ends (i.e. at least 2).
+    #[must_use]
+    pub fn with_ticks(mut self, x: usize, y: usize) -> Self {
+        self.ticks = (x.max(2), y.max(2));
+        self
+    }
+
+    /// Sets whether the legend is shown; defaults to `true`.
+    #[must_use]
+    pub fn with_legend(mut self, legend: bool) -> Self {
+        self.legend = legend;
+        self
+    }
+
+    /// Returns the series of the plot.
+    #[must_use]
+    pub fn series(&self) -> &[Series] {
+        &self.series
+    }
+
+    /// Returns the `(min, max)` ranges of the X and Y axes, as drawn.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use grux::art::{Plot, Series};
+    /// let plot = Plot::new(20, 10)
+    ///     .with_series(Series::new("a", [(1.0, 2.0), (3.0, 2.0)]))
+    ///     .with_y_range(0.0, 5.0);
+    ///
+    /// assert_eq!(plot.ranges(), ((1.0, 3.0), (0.0, 5.0)));
+    /// ```
+    #[must_use]
+    pub fn ranges(&self) -> ((f64, f64), (f64, f64)) {
+        let points = self
+            .series
+            .iter()
+            .flat_map(|series| series.points.iter().copied());
+        let x = self
+            .x_range
+            .unwrap_or_else(|| bounds(points.clone().map(|(x, _)| x)));
+        let y = self
+            .y_range
+            .unwrap_or_else(|| bounds(points.map(|(_, y)| y)));
+        (widen(x), widen(y))
+    }
+}
+
+/// Returns the smallest and largest of the values, ignoring `NaN` and infinities, or `(0.0, 1.0)`
+/// if there are none.
+fn bounds(values: impl Iterator<Item = f64>) -> (f64, f64) {
+    let (min, max) = values
+        .filter(|value| value.is_finite())
+        .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), value| {
+            (min.min(value), max.max(value))
+        });
+    if min > max {
+        (0.0, 1.0)
+    } else {
+        (min, max)
+    }
+}
+
+/// Widens an empty range, so that values can be scaled to it.
+fn widen((min, max): (f64, f64)) -> (f64, f64) {
+    if max > min {
+        (min, max)
+    } else {
+        (min - 1.0, min + 1.0)
+    }
+}
+
+/// Rounds to the nearest integer, saturating at the bounds of `isize`, since `f64::round` requires
+/// `std`.
+fn round(value: f64) -> isize {
+    let value = value + 0.5;
+    let truncated = value as isize;
+    if (truncated as f64) > value {
+        truncated.saturating_sub(1)
+    } else {
+        truncated
+    }
+}
+
+/// Clips the line between two points to the given `(x, y)` ranges, i.e. Liang–Barsky, returning
+/// `None` if none of the line is inside the ranges.
+fn clip(
+    from: (f64, f64),
+    to: (f64, f64),
+    ((x_min, x_max), (y_min, y_max)): ((f64, f64), (f64, f64)),
+) -> Option<((f64, f64), (f64, f64))> {
+    let (dx, dy) = (to.0 - from.0, to.1 - from.1);
+    let (mut start, mut end) = (0.0_f64, 1.0_f64);
+    for (p, q) in [
+        (-dx, from.0 - x_min),
+        (dx, x_max - from.0),
+        (-dy, from.1 - y_min),
+        (dy, y_max - from.1),
+    ] {
+        if p == 0.0 {
+            if q < 0.0 {
+                return None;
+            }
+        } else if p < 0.0 {
+            start = start.max(q / p);
+        } else {
+            end = end.min(q / p);
+        }
+    }
+    (start <= end).then_some({
+        (
+            (from.0 + start * dx, from.1 + start * dy),
+            (from.0 + end * dx, from.1 + end * dy),
+        )
+    })
+}
+
+/// Returns the `(offset, value)` of each of `count` evenly spaced ticks over `cells` cells.
+fn ticks(range: (f64, f64), count: usize, cells: usize) -> impl Iterator<Item = (usize, f64)> {
+    let last = (count - 1) as f64;
+    (0..count).map(move |i| {
+        let fraction = i as f64 / last;
+        let offset = round(fraction * cells.saturating_sub(1) as f64) as usize;
+        (offset, range.0 + (range.1 - range.0) * fraction)
+    })
+}
+
+/// Returns the difference between the values of consecutive ticks.
+fn step(range: (f64, f64), count: usize) -> f64 {
+    (range.1 - range.0) / (count - 1) as f64
+}
+
+/// Formats a tick label, with enough decimals to tell ticks `step` apart.
+fn tick_label(value: f64, step: f64) -> String {
+    let precision = if step >= 1.0 {
+        0
+    } else if step >= 0.1 {
+        1
+    } else {
+        2
+    };
+    let label = format!("{value:.precision$}");
+    match label.strip_prefix('-') {
+        Some(digits) if digits.chars().all(|c| c == '0' || c == '.') => String::from(digits),
+        _ => label,
+    }
+}
+
+impl Sprite for Plot {
+    type Element = char;
+
+    fn width(&self) -> usize {
+        self.width
+    }
+
+    fn height(&self) -> usize {
+        self.height
+    }
+
+    fn draw_to(&self, position: (usize, usize), to: &mut impl GridWriter<Element = Self::Element>) {
+        let (x, y) = position;
+        for row in 0..self.height {
+            for column in 0..self.width {
+                to.set((x + column, y + row), ' ');
+            }
+        }
+
+        let (x_range, y_range) = self.ranges();
+        let rows = self.height.saturating_sub(2);
+        if rows == 0 {
+            return;
+        }
+        let y_ticks: Vec<(usize, String)> = ticks(y_range, self.ticks.1, rows)
+            .map(|(offset, value)| {
+                (
+                    rows - 1 - offset,
+                    tick_label(value, step(y_range, self.ticks.1)),
+                )
+            })
+            .collect();
+        let axis = y_ticks
+            .iter()
+            .map(|(_, label)| label.chars().count())
+            .max()
+            .unwrap_or(0);
+        let columns = self.width.saturating_sub(axis + 1);
+        if columns == 0 {
+            return;
+        }
+        let (left, bottom) = (x + axis + 1, y + rows);
+
+        // The Y axis, with its ticks and right-aligned labels.
+        Line::vertical(rows, '│').draw_to((x + axis, y), to);
+        for (row, label) in &y_ticks {
+            let start = axis - label.chars().count();
+            for (i, c) in label.chars().enumerate() {
+                to.set((x + start + i, y + row), c);
+            }
+            to.set((x + axis, y + row), '┤');
+        }
+
+        // The X axis, with its ticks and labels centered under them where they fit.
+        Line::horizontal(columns, '─').draw_to((left, bottom), to);
+        to.set((x + axis, bottom), '└');
+        let mut free = 0;
+        for (offset, value) in ticks(x_range, self.ticks.0, columns) {
+            to.set((left + offset, bottom), '┬');
+            let label = tick_label(value, step(x_range, self.ticks.0));
+            let len = label.chars().count();
+            let start = (axis + 1 + offset)
+                .saturating_sub(len / 2)
+                .min(self.width.saturating_sub(len));
+            if start >= free {
+                for (i, c) in label.chars().enumerate() {
+                    to.set((x + start + i, bottom + 1), c);
+                }
+                free = start + len + 1;
+            }
+        }
+
+        // Braille series share a canvas, which is drawn below the other series.
+        let mut canvas = BrailleCanvas::new(columns, rows);
+        let pixels = canvas.pixel_size();
+        for series in self.series.iter().filter(|s| s.marker == Marker::Braille) {
+            series.for_each_point((x_range, y_range), pixels, |p| canvas.set_signed_pixel(p));
+        }
+        for row in 0..rows {
+            for column in 0..columns {
+                match canvas.cell((column, row)) {
+                    Some('\u{2800}') | None => {}
+                    Some(c) => to.set((left + column, y + row), c),
+                }
+            }
+        }
+
+        let mut set_cell = |(column, row): (isize, isize), c: char| {
+            if let (Ok(column), Ok(row)) = (usize::try_from(column), usize::try_from(row)) {
+                if column < columns && row < rows {
+                    to.set((left + column, y + row), c);
+                }
+            }
+        };
+        for series in &self.series {
+            let Marker::Char(marker) = series.marker else {
+                continue;
+            };
+            series.for_each_point((x_range, y_range), (columns, rows), |p| {
+                set_cell(p, marker);
+            });
+        }
+
+        // The legend, right-aligned in the top right corner.
+        if !self.legend {
+            return;
+        }
+        let labelled = self.series.iter().filter(|s| !s.label.is_empty());
+        for (row, series) in labelled.take(rows).enumerate() {
+            let len = (series.label.chars().count() + 2).min(columns);
+            let entry = [series.marker.legend(), ' ']
+                .into_iter()
+                .chain(series.label.chars());
+            for (i, c) in entry.take(len).enumerate() {
+                to.set((left + columns - len + i, y + row), c);
+            }
+        }
+    }
+}
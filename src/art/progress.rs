@@ -0,0 +1,322 @@
+use alloc::{format, string::String};
+
+use crate::{
+    art::{
+        chart::{eighths, scale, HORIZONTAL_EIGHTHS},
+        BrailleCanvas, Sprite,
+    },
+    GridWriter,
+};
+
+/// Returns the text shown for a value: the label and/or the value as a percentage.
+fn text(label: &str, percentage: bool, value: f64) -> String {
+    let percent = scale(value, 100);
+    match (label.is_empty(), percentage) {
+        (_, false) => String::from(label),
+        (true, true) => format!("{percent}%"),
+        (false, true) => format!("{label} {percent}%"),
+    }
+}
+
+/// Draws text centered in a row of `width` cells, truncated if it does not fit.
+fn draw_centered(
+    text: &str,
+    (x, y): (usize, usize),
+    width: usize,
+    to: &mut impl GridWriter<Element = char>,
+) {
+    let len = text.chars().count().min(width);
+    let start = x + (width - len) / 2;
+    for (i, c) in text.chars().take(len).enumerate() {
+        to.set((start + i, y), c);
+    }
+}
+
+/// A horizontal progress bar, filled to a value between `0.0` and `1.0`.
+///
+/// By default, the bar is filled with `█` and the end of the fill is drawn with a partial block
+/// (e.g. `▌`), so that progress is shown in eighths of a cell; with other glyphs, the fill is
+/// rounded to whole cells. Text, i.e. a label and/or the percentage, is drawn centered over the
+/// bar.
+///
+/// # Examples
+///
+/// ```
+/// # use grux::art::{ProgressBar, Sprite};
+/// let bar = ProgressBar::new(10, 0.45);
+///
+/// let mut grid = [[' '; 10]; 1];
+/// bar.draw_to((0, 0), &mut grid);
+///
+/// assert_eq!(String::from_iter(grid[0]), "████▌     ");
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ProgressBar {
+    width: usize,
+    value: f64,
+    label: String,
+    percentage: bool,
+    fill: char,
+    empty: char,
+    ends: Option<(char, char)>,
+}
+
+impl ProgressBar {
+    /// Configures a progress bar of the given width, filled to the given value.
+    ///
+    /// Values are clamped to `0.0..=1.0`.
+    #[must_use]
+    pub fn new(width: usize, value: f64) -> Self {
+        Self {
+            width,
+            value,
+            label: String::new(),
+            percentage: false,
+            fill: '█',
+            empty: ' ',
+            ends: None,
+        }
+    }
+
+    /// Sets the value the bar is filled to.
+    pub fn set_value(&mut self, value: f64) {
+        self.value = value;
+    }
+
+    /// Returns the value the bar is filled to.
+    #[must_use]
+    pub fn value(&self) -> f64 {
+        self.value
+    }
+
+    /// Sets a label that is drawn centered over the bar.
+    #[must_use]
+    pub fn with_label(mut self, label: impl Into<String>) -> Self {
+        self.label = label.into();
+        self
+    }
+
+    /// Sets whether the value is drawn as a percentage (after the label, if any).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use grux::art::{ProgressBar, Sprite};
+    /// let bar = ProgressBar::new(16, 0.5)
+    ///     .with_label("copy")
+    ///     .with_percentage(true)
+    ///     .with_glyphs('=', '-')
+    ///     .with_ends('[', ']');
+    ///
+    /// let mut grid = [[' '; 16]; 1];
+    /// bar.draw_to((0, 0), &mut grid);
+    ///
+    /// assert_eq!(String::from_iter(grid[0]), "[===copy 50%---]");
+    /// ```
+    #[must_use]
+    pub fn with_percentage(mut self, percentage: bool) -> Self {
+        self.percentage = percentage;
+        self
+    }
+
+    /// Sets the glyphs of the filled and empty parts of the bar; defaults to `█` and a space.
+    ///
+    /// Partial blocks are only drawn if the fill is `█`.
+    #[must_use]
+    pub fn with_glyphs(mut self, fill: char, empty: char) -> Self {
+        self.fill = fill;
+        self.empty = empty;
+        self
+    }
+
+    /// Sets the glyphs drawn at the left and right ends of the bar, inside its width.
+    #[must_use]
+    pub fn with_ends(mut self, left: char, right: char) -> Self {
+        self.ends = Some((left, right));
+        self
+    }
+}
+
+impl Sprite for ProgressBar {
+    type Element = char;
+
+    fn width(&self) -> usize {
+        self.width
+    }
+
+    fn height(&self) -> usize {
+        1
+    }
+
+    fn draw_to(&self, position: (usize, usize), to: &mut impl GridWriter<Element = Self::Element>) {
+        let (mut x, y) = position;
+        let mut cells = self.width;
+        if let Some((left, right)) = self.ends {
+            if cells < 2 {
+                return;
+            }
+            to.set((x, y), left);
+            to.set((x + cells - 1, y), right);
+            x += 1;
+            cells -= 2;
+        }
+
+        let filled = if self.fill == '█' {
+            scale(self.value, cells * 8)
+        } else {
+            scale(self.value, cells) * 8
+        };
+        for i in 0..cells {
+            let c = match filled.saturating_sub(i * 8) {
+                0 => self.empty,
+                8.. => self.fill,
+                partial => eighths(&HORIZONTAL_EIGHTHS, partial),
+            };
+            to.set((x + i, y), c);
+        }
+        draw_centered(
+            &text(&self.label, self.percentage, self.value),
+            (x, y),
+            cells,
+            to,
+        );
+    }
+}
+
+/// A semicircular gauge, filled clockwise from the left to a value between `0.0` and `1.0`.
+///
+/// The gauge is drawn with braille dots (see [`BrailleCanvas`]): the filled part of the arc is a
+/// thick band, and the rest is a thin outline. The arc is as large as fits, with its base on the
+/// bottom row; text, i.e. a label and/or the percentage, is drawn centered on the row below it.
+///
+/// # Examples
+///
+/// ```
+/// # #[cfg(feature = "std")]
+/// # {
+/// # use grux::DisplayGrid;
+/// # use grux::art::{Gauge, Sprite};
+/// let gauge = Gauge::new(8, 3, 0.5).with_percentage(true);
+///
+/// let mut grid = [[' '; 8]; 3];
+/// gauge.draw_to((0, 0), &mut grid);
+///
+/// assert_eq!(grid.to_string().unwrap(), concat!(
+///     " ⣴⣾⠿⠉⠓⢦ \n",
+///     "⣸⣿⠁    ⣇\n",
+///     "  50%   \n",
+/// ));
+/// # }
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Gauge {
+    width: usize,
+    height: usize,
+    value: f64,
+    label: String,
+    percentage: bool,
+}
+
+impl Gauge {
+    /// Configures a gauge of the given size, in cells, filled to the given value.
+    ///
+    /// Values are clamped to `0.0..=1.0`.
+    #[must_use]
+    pub fn new(width: usize, height: usize, value: f64) -> Self {
+        Self {
+            width,
+            height,
+            value,
+            label: String::new(),
+            percentage: false,
+        }
+    }
+
+    /// Sets the value the gauge is filled to.
+    pub fn set_value(&mut self, value: f64) {
+        self.value = value;
+    }
+
+    /// Returns the value the gauge is filled to.
+    #[must_use]
+    pub fn value(&self) -> f64 {
+        self.value
+    }
+
+    /// Sets a label that is drawn centered below the arc.
+    #[must_use]
+    pub fn with_label(mut self, label: impl Into<String>) -> Self {
+        self.label = label.into();
+        self
+    }
+
+    /// Sets whether the value is drawn as a percentage (after the label, if any).
+    #[must_use]
+    pub fn with_percentage(mut self, percentage: bool) -> Self {
+        self.percentage = percentage;
+        self
+    }
+
+    /// Returns the canvas with the arc, which fills the rows above the text, if any.
+    fn arc(&self, text: &str) -> BrailleCanvas {
+        let rows = self.height - usize::from(!text.is_empty());
+        let mut canvas = BrailleCanvas::new(self.width, rows);
+        let (width, height) = canvas.pixel_size();
+        if width == 0 || height == 0 {
+            return canvas;
+        }
+
+        // The center of the arc is in the middle of the bottom row of pixels.
+        let (cx, cy) = ((width - 1) as f64 / 2.0, (height - 1) as f64);
+        let radius = cx.min(cy) + 0.5;
+        let inner = radius * 0.6;
+        let value = scale(self.value, 1000);
+        for py in 0..height {
+            for px in 0..width {
+                let (dx, dy) = (px as f64 - cx, py as f64 - cy);
+                let distance = dx * dx + dy * dy;
+                if distance > radius * radius || distance < inner * inner {
+                    continue;
+                }
+
+                // The "diamond angle" of the pixel, from 0 (left) to 1 (right), which is close
+                // enough to the real angle without needing trigonometry (i.e. `std`).
+                let angle = (dx / (dx.abs() + dy.abs()) + 1.0) / 2.0;
+                let edge = (radius - 1.0) * (radius - 1.0);
+                if scale(angle, 1000) <= value && value > 0 || distance >= edge {
+                    canvas.set_pixel((px, py));
+                }
+            }
+        }
+        canvas
+    }
+}
+
+impl Sprite for Gauge {
+    type Element = char;
+
+    fn width(&self) -> usize {
+        self.width
+    }
+
+    fn height(&self) -> usize {
+        self.height
+    }
+
+    fn draw_to(&self, position: (usize, usize), to: &mut impl GridWriter<Element = Self::Element>) {
+        let (x, y) = position;
+        if self.height == 0 {
+            return;
+        }
+        let text = text(&self.label, self.percentage, self.value);
+        self.arc(&text).draw_to(position, to);
+        if !text.is_empty() {
+            for column in 0..self.width {
+                to.set((x + column, y + self.height - 1), ' ');
+            }
+            draw_centered(&text, (x, y + self.height - 1), self.width, to);
+        }
+    }
+}
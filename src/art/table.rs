@@ -0,0 +1,393 @@
+use alloc::{string::String, vec, vec::Vec};
+
+use crate::{
+    art::{BorderRect, Line, Sprite},
+    box_drawing::{self, Arms},
+    GridWriter,
+};
+
+/// How the width of a column of a [`Table`] is computed, not including its padding.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ColumnWidth {
+    /// Exactly this many cells.
+    Fixed(usize),
+
+    /// As wide as the widest text in the column (including its header), within `min..=max`.
+    Auto {
+        /// The minimum width.
+        min: usize,
+
+        /// The maximum width; longer text is truncated.
+        max: usize,
+    },
+
+    /// A share of the width that is left after the other columns, relative to the shares of the
+    /// other proportional columns; see [`Table::with_width`].
+    ///
+    /// Without a width for the table, the column is as wide as its widest text.
+    Proportional(usize),
+}
+
+/// Fits the widest text in the column.
+impl Default for ColumnWidth {
+    fn default() -> Self {
+        ColumnWidth::Auto {
+            min: 0,
+            max: usize::MAX,
+        }
+    }
+}
+
+/// How the text of a column of a [`Table`] is aligned.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Alignment {
+    /// Aligned to the left, padded on the right.
+    #[default]
+    Left,
+
+    /// Centered, with any odd padding on the right.
+    Center,
+
+    /// Aligned to the right, padded on the left.
+    Right,
+}
+
+/// A table of text, with an optional header row, drawn with a border and separators.
+///
+/// The border is a set of characters in the same order as [`BorderRect::new`], which are also used
+/// for the separators between columns (the left border) and below the header (the top border).
+/// The junctions where separators meet the border, e.g. `┬` and `┼`, are found from the lines
+/// that the characters are made of; if they are not box-drawing characters, the top-left corner
+/// is used instead, e.g. `+` for ASCII borders.
+///
+/// Text that is wider than its column is truncated with `…`.
+///
+/// # Examples
+///
+/// ```
+/// # #[cfg(feature = "std")]
+/// # {
+/// # use grux::DisplayGrid;
+/// # use grux::art::{Alignment, Sprite, Table};
+/// let table = Table::new(["name", "size"])
+///     .with_row(["grux", "12"])
+///     .with_row(["readme", "3"])
+///     .with_alignment(1, Alignment::Right);
+///
+/// let mut grid = vec![vec![' '; table.width()]; table.height()];
+/// table.draw_to((0, 0), &mut grid);
+///
+/// assert_eq!(grid.to_string().unwrap(), concat!(
+///     "┌────────┬──────┐\n",
+///     "│ name   │ size │\n",
+///     "├────────┼──────┤\n",
+///     "│ grux   │   12 │\n",
+///     "│ readme │    3 │\n",
+///     "└────────┴──────┘\n",
+/// ));
+/// # }
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Table {
+    headers: Vec<String>,
+    rows: Vec<Vec<String>>,
+    widths: Vec<ColumnWidth>,
+    alignments: Vec<Alignment>,
+    width: Option<usize>,
+    padding: usize,
+    border: [char; 8],
+}
+
+impl Table {
+    /// Configures a table with the given headers, and no rows.
+    ///
+    /// If there are no headers, the table has no header row, and as many columns as its longest
+    /// row.
+    #[must_use]
+    pub fn new<S: Into<String>>(headers: impl IntoIterator<Item = S>) -> Self {
+        Self {
+            headers: headers.into_iter().map(Into::into).collect(),
+            rows: Vec::new(),
+            widths: Vec::new(),
+            alignments: Vec::new(),
+            width: None,
+            padding: 1,
+            border: ['┌', '─', '┐', '│', '│', '└', '─', '┘'],
+        }
+    }
+
+    /// Adds a row; missing cells are empty, and cells past the last column are ignored.
+    #[must_use]
+    pub fn with_row<S: Into<String>>(mut self, row: impl IntoIterator<Item = S>) -> Self {
+        self.rows.push(row.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Sets how the width of the column at the given index is computed; defaults to
+    /// [`ColumnWidth::default`].
+    #[must_use]
+    pub fn with_column_width(mut self, column: usize, width: ColumnWidth) -> Self {
+        if self.widths.len() <= column {
+            self.widths.resize(column + 1, ColumnWidth::default());
+        }
+        self.widths[column] = width;
+        self
+    }
+
+    /// Sets how the text of the column at the given index is aligned; defaults to the left.
+    #[must_use]
+    pub fn with_alignment(mut self, column: usize, alignment: Alignment) -> Self {
+        if self.alignments.len() <= column {
+            self.alignments.resize(column + 1, Alignment::default());
+        }
+        self.alignments[column] = alignment;
+        self
+    }
+
+    /// Sets the total width of the table, in cells, which [`ColumnWidth::Proportional`] columns
+    /// are sized to fill.
+    ///
+    /// Without proportional columns, or if the other columns are too wide, the width of the table
+    /// is the sum of its columns.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[cfg(feature = "std")]
+    /// # {
+    /// # use grux::DisplayGrid;
+    /// # use grux::art::{Alignment, ColumnWidth, Sprite, Table};
+    /// let table = Table::new(["id", "description"])
+    ///     .with_row(["1", "a very long description"])
+    ///     .with_column_width(1, ColumnWidth::Proportional(1))
+    ///     .with_alignment(0, Alignment::Center)
+    ///     .with_width(20)
+    ///     .with_border(['+', '-', '+', '|', '|', '+', '-', '+']);
+    ///
+    /// let mut grid = vec![vec![' '; table.width()]; table.height()];
+    /// table.draw_to((0, 0), &mut grid);
+    ///
+    /// assert_eq!(grid.to_string().unwrap(), concat!(
+    ///     "+----+-------------+\n",
+    ///     "| id | description |\n",
+    ///     "+----+-------------+\n",
+    ///     "| 1  | a very lon… |\n",
+    ///     "+----+-------------+\n",
+    /// ));
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn with_width(mut self, width: usize) -> Self {
+        self.width = Some(width);
+        self
+    }
+
+    /// Sets the number of spaces on each side of the text in a column; defaults to 1.
+    #[must_use]
+    pub fn with_padding(mut self, padding: usize) -> Self {
+        self.padding = padding;
+        self
+    }
+
+    /// Sets the characters of the border, in the same order as [`BorderRect::new`]; defaults to
+    /// light box-drawing lines, i.e. `┌─┐││└─┘`.
+    #[must_use]
+    pub fn with_border(mut self, border: [char; 8]) -> Self {
+        self.border = border;
+        self
+    }
+
+    /// Returns the headers of the table.
+    #[must_use]
+    pub fn headers(&self) -> &[String] {
+        &self.headers
+    }
+
+    /// Returns the rows of the table.
+    #[must_use]
+    pub fn rows(&self) -> &[Vec<String>] {
+        &self.rows
+    }
+
+    /// Returns the number of columns.
+    fn columns(&self) -> usize {
+        if self.headers.is_empty() {
+            self.rows.iter().map(Vec::len).max().unwrap_or(0)
+        } else {
+            self.headers.len()
+        }
+    }
+
+    /// Returns the width of each column, not including padding.
+    fn column_widths(&self) -> Vec<usize> {
+        let columns = self.columns();
+        let natural = |column: usize| {
+            let header = self.headers.get(column).into_iter();
+            let cells = self.rows.iter().filter_map(|row| row.get(column));
+            header
+                .chain(cells)
+                .map(|text| text.chars().count())
+                .max()
+                .unwrap_or(0)
+        };
+
+        let mut widths = vec![0; columns];
+        let mut shares = 0;
+        for (column, width) in widths.iter_mut().enumerate() {
+            *width = match self.widths.get(column).copied().unwrap_or_default() {
+                ColumnWidth::Fixed(width) => width,
+                ColumnWidth::Auto { min, max } => natural(column).min(max).max(min),
+                ColumnWidth::Proportional(share) => {
+                    shares += share;
+                    continue;
+                }
+            };
+        }
+
+        let proportional = (0..columns).filter_map(|column| match self.widths.get(column) {
+            Some(ColumnWidth::Proportional(share)) => Some((column, *share)),
+            _ => None,
+        });
+        match self.width {
+            Some(total) if shares > 0 => {
+                let used = widths.iter().sum::<usize>() + self.frame_width(columns);
+                let remaining = total.saturating_sub(used);
+                for (column, share) in proportional.clone() {
+                    widths[column] = remaining * share / shares;
+                }
+
+                // Cells that are left over from rounding down go to the first columns.
+                let given = proportional.clone().map(|(column, _)| widths[column]);
+                let mut left_over = remaining - given.sum::<usize>();
+                for (column, share) in proportional {
+                    if left_over > 0 && share > 0 {
+                        widths[column] += 1;
+                        left_over -= 1;
+                    }
+                }
+            }
+            _ => {
+                for (column, _) in proportional {
+                    widths[column] = natural(column);
+                }
+            }
+        }
+        widths
+    }
+
+    /// Returns the width of the border, separators and padding of the given number of columns.
+    fn frame_width(&self, columns: usize) -> usize {
+        match columns {
+            0 => 2,
+            n => n * (self.padding * 2 + 1) + 1,
+        }
+    }
+
+    /// Returns the junction character with the given lines, or the top-left corner if there
+    /// isn't one.
+    fn junction(&self, arms: Arms) -> char {
+        box_drawing::encode(arms).unwrap_or(self.border[0])
+    }
+}
+
+/// Truncates the text to the width, with an ellipsis, and aligns it within the width.
+pub(super) fn align(
+    text: &str,
+    width: usize,
+    alignment: Alignment,
+) -> (usize, impl Iterator<Item = char> + '_) {
+    let len = text.chars().count();
+    let (shown, ellipsis) = if len > width {
+        (width.saturating_sub(1), width > 0)
+    } else {
+        (len, false)
+    };
+    let total = shown + usize::from(ellipsis);
+    let offset = match alignment {
+        Alignment::Left => 0,
+        Alignment::Center => (width - total) / 2,
+        Alignment::Right => width - total,
+    };
+    let chars = text.chars().take(shown).chain(ellipsis.then_some('…'));
+    (offset, chars)
+}
+
+impl Sprite for Table {
+    type Element = char;
+
+    fn width(&self) -> usize {
+        let columns = self.columns();
+        self.column_widths().iter().sum::<usize>() + self.frame_width(columns)
+    }
+
+    fn height(&self) -> usize {
+        let header = if self.headers.is_empty() { 0 } else { 2 };
+        self.rows.len() + header + 2
+    }
+
+    fn draw_to(&self, position: (usize, usize), to: &mut impl GridWriter<Element = Self::Element>) {
+        let (x, y) = position;
+        let (width, height) = (self.width(), self.height());
+        let widths = self.column_widths();
+        let has_header = !self.headers.is_empty();
+        for row in 1..height - 1 {
+            for column in 1..width - 1 {
+                to.set((x + column, y + row), ' ');
+            }
+        }
+        BorderRect::new(width, height, self.border).draw_to(position, to);
+
+        // The weights of the lines of the border; separators between columns use the left border,
+        // and the separator below the header uses the top border.
+        let weight = |c: char, arm: usize| box_drawing::decode(c).and_then(|arms| arms[arm]);
+        let [_, top, _, left, right, _, bottom, _] = self.border;
+        let (h_top, h_bottom) = (weight(top, 1), weight(bottom, 1));
+        let (v_left, v_right) = (weight(left, 0), weight(right, 0));
+
+        // The separator below the header.
+        if has_header {
+            Line::horizontal(width - 2, top).draw_to((x + 1, y + 2), to);
+            to.set((x, y + 2), self.junction([v_left, h_top, v_left, None]));
+            to.set(
+                (x + width - 1, y + 2),
+                self.junction([v_right, None, v_right, h_top]),
+            );
+        }
+
+        // The separators between columns, and the text of each column.
+        let mut start = x + 1;
+        for (column, &column_width) in widths.iter().enumerate() {
+            let alignment = self.alignments.get(column).copied().unwrap_or_default();
+            let header = has_header.then(|| (1, self.headers[column].as_str()));
+            let body = if has_header { 3 } else { 1 };
+            let cells = self
+                .rows
+                .iter()
+                .enumerate()
+                .map(|(i, row)| (body + i, row.get(column).map_or("", String::as_str)));
+            for (row, text) in header.into_iter().chain(cells) {
+                let (offset, chars) = align(text, column_width, alignment);
+                for (i, c) in chars.enumerate() {
+                    to.set((start + self.padding + offset + i, y + row), c);
+                }
+            }
+
+            let end = start + column_width + self.padding * 2;
+            if column + 1 < widths.len() {
+                Line::vertical(height - 2, left).draw_to((end, y + 1), to);
+                to.set((end, y), self.junction([None, h_top, v_left, h_top]));
+                to.set(
+                    (end, y + height - 1),
+                    self.junction([v_left, h_bottom, None, h_bottom]),
+                );
+                if has_header {
+                    to.set((end, y + 2), self.junction([v_left, h_top, v_left, h_top]));
+                }
+            }
+            start = end + 1;
+        }
+    }
+}
@@ -0,0 +1,391 @@
+//! Record grids as [asciicast v2][] files, e.g. to attach animated output to bug reports.
+//!
+//! [`grux::asciicast`][`crate::asciicast`] provides:
+//! - The header of a recording, i.e. the terminal size and metadata: [`Header`].
+//! - A writer of successive grid frames, with timestamps: [`Recorder`].
+//! - A reader that replays a recording back into frames, e.g. for tests: [`Recording`].
+//!
+//! Recordings can be played back with [asciinema](https://asciinema.org/), or embedded in web
+//! pages with its player.
+//!
+//! [asciicast v2]: https://docs.asciinema.org/manual/asciicast/v2/
+//!
+//! # Examples
+//!
+//! ```
+//! use std::time::Duration;
+//!
+//! use grux::GridWriter;
+//! use grux::asciicast::{Header, Recorder, Recording};
+//!
+//! let mut recorder = Recorder::new(Vec::new(), &Header::new(3, 1)).unwrap();
+//! let mut grid = [['.'; 3]; 1];
+//!
+//! for i in 0..3 {
+//!     grid.set((i, 0), '#');
+//!     recorder.frame(Duration::from_millis(100 * i as u64), &grid).unwrap();
+//! }
+//!
+//! let file = recorder.into_inner();
+//! let recording = Recording::read(&mut file.as_slice()).unwrap();
+//!
+//! assert_eq!(recording.frames.len(), 3);
+//! assert_eq!(recording.frames[1].time, Duration::from_millis(100));
+//! assert_eq!(recording.frames[1].text(), "##.\n");
+//! ```
+
+use std::{
+    fmt::Write as _,
+    io::{self, BufRead, Write},
+    time::Duration,
+};
+
+use crate::{
+    grid::Grid,
+    json,
+    style::{Style, Styled},
+    DisplayGrid,
+};
+
+/// The escape codes written before each frame: clear the screen, and move the cursor home.
+const CLEAR: &str = "\x1b[2J\x1b[H";
+
+/// The largest terminal, in cells, that [`Recording::read`] replays.
+const MAX_SCREEN_CELLS: usize = 1 << 22;
+
+/// The header of an asciicast recording.
+///
+/// # Examples
+///
+/// ```
+/// # use grux::asciicast::Header;
+/// let header = Header {
+///     title: Some("Loading screen".to_string()),
+///     ..Header::new(80, 24)
+/// };
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Header {
+    /// The width of the terminal, in columns.
+    pub width: usize,
+
+    /// The height of the terminal, in rows.
+    pub height: usize,
+
+    /// When the recording started, as seconds since the Unix epoch; defaults to `None`.
+    pub timestamp: Option<u64>,
+
+    /// The title of the recording; defaults to `None`.
+    pub title: Option<String>,
+}
+
+impl Header {
+    /// Creates a header for a terminal of the given size.
+    #[must_use]
+    pub fn new(width: usize, height: usize) -> Self {
+        Self {
+            width,
+            height,
+            timestamp: None,
+            title: None,
+        }
+    }
+}
+
+/// Writes successive grid frames as an asciicast v2 recording.
+///
+/// Each frame clears the screen and writes the grid, as written by [`DisplayGrid::write_to`], so
+/// styled cells keep their colors; the grid should fit the size in the [`Header`].
+#[derive(Debug)]
+pub struct Recorder<W: Write> {
+    stream: W,
+    time: Duration,
+}
+
+impl<W: Write> Recorder<W> {
+    /// Creates a recorder, writing the header to the given output stream.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the output stream returns an error.
+    pub fn new(mut stream: W, header: &Header) -> io::Result<Self> {
+        let mut line = format!(
+            r#"{{"version": 2, "width": {}, "height": {}"#,
+            header.width, header.height
+        );
+        if let Some(timestamp) = header.timestamp {
+            let _ = write!(line, r#", "timestamp": {}"#, timestamp);
+        }
+        if let Some(title) = &header.title {
+            line.push_str(r#", "title": "#);
+            json::write_string(&mut line, title);
+        }
+        line.push('}');
+        writeln!(stream, "{}", line)?;
+        Ok(Self {
+            stream,
+            time: Duration::ZERO,
+        })
+    }
+
+    /// Writes a frame, shown at the given time since the start of the recording.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the output stream returns an error, or the time is earlier than the time
+    /// of the previous frame.
+    pub fn frame(&mut self, time: Duration, grid: &impl DisplayGrid) -> io::Result<()> {
+        if time < self.time {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "frames must be recorded in order",
+            ));
+        }
+        self.time = time;
+
+        let mut output = Vec::new();
+        grid.write_to(&mut output)?;
+        let output = String::from_utf8_lossy(&output);
+
+        // A newline after the last row would scroll the screen, so it is omitted.
+        let output = output.strip_suffix('\n').unwrap_or(&output);
+        let mut line = format!("[{}.{:06}, \"o\", ", time.as_secs(), time.subsec_micros());
+        json::write_string(
+            &mut line,
+            &(CLEAR.to_string() + &output.replace('\n', "\r\n")),
+        );
+        line.push(']');
+        writeln!(self.stream, "{}", line)
+    }
+
+    /// Returns the underlying output stream.
+    #[must_use]
+    pub fn into_inner(self) -> W {
+        self.stream
+    }
+}
+
+/// A frame of a replayed recording: the screen after an output event.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Frame {
+    /// The time of the event, since the start of the recording.
+    pub time: Duration,
+
+    /// The contents of the screen.
+    pub screen: Grid<Styled>,
+}
+
+impl Frame {
+    /// Returns the characters on the screen, without styles, with a newline after each row.
+    #[must_use]
+    pub fn text(&self) -> String {
+        self.screen
+            .rows()
+            .flat_map(|row| row.iter().map(|cell| cell.value).chain(['\n']))
+            .collect()
+    }
+}
+
+/// An asciicast v2 recording, replayed into frames.
+///
+/// Output events are replayed on a virtual screen of the size in the [`Header`], which understands
+/// cursor movement, erasing, and colors; other events (e.g. input) are ignored.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Recording {
+    /// The header of the recording.
+    pub header: Header,
+
+    /// The frames of the recording, one per output event.
+    pub frames: Vec<Frame>,
+}
+
+impl Recording {
+    /// Reads and replays a recording from the given input stream.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the input stream returns an error, the recording is malformed, or its
+    /// terminal is larger than 4,194,304 cells (e.g. 2048×2048).
+    pub fn read(stream: &mut impl BufRead) -> io::Result<Self> {
+        let invalid = |message: &str| io::Error::new(io::ErrorKind::InvalidData, message);
+        let mut lines = stream.lines();
+
+        let header = lines.next().ok_or_else(|| invalid("missing header"))??;
+        let header = json::parse(&header).ok_or_else(|| invalid("malformed header"))?;
+        if header.get("version").and_then(json::Value::as_f64) != Some(2.0) {
+            return Err(invalid("unsupported version"));
+        }
+        let number = |key: &str| header.get(key).and_then(json::Value::as_f64);
+        let size = |key: &str| {
+            number(key)
+                .filter(|n| n.fract() == 0.0 && *n >= 0.0)
+                .map(|n| n as usize)
+                .ok_or_else(|| invalid("malformed terminal size"))
+        };
+        let header = Header {
+            width: size("width")?,
+            height: size("height")?,
+            timestamp: number("timestamp").map(|n| n as u64),
+            title: header
+                .get("title")
+                .and_then(json::Value::as_str)
+                .map(str::to_string),
+        };
+
+        header
+            .width
+            .checked_mul(header.height)
+            .filter(|&cells| cells <= MAX_SCREEN_CELLS)
+            .ok_or_else(|| invalid("terminal size too large"))?;
+
+        let mut screen = Screen::new(header.width, header.height);
+        let mut frames = Vec::new();
+        for line in lines {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let event = json::parse(&line).ok_or_else(|| invalid("malformed event"))?;
+            let (time, kind, data) = match event.as_array() {
+                Some([time, kind, data]) => (time.as_f64(), kind.as_str(), data.as_str()),
+                _ => return Err(invalid("malformed event")),
+            };
+            let time = time
+                .and_then(|time| Duration::try_from_secs_f64(time).ok())
+                .ok_or_else(|| invalid("malformed event time"))?;
+            if kind == Some("o") {
+                screen.write(data.ok_or_else(|| invalid("malformed event data"))?);
+                frames.push(Frame {
+                    time,
+                    screen: screen.snapshot(),
+                });
+            }
+        }
+
+        Ok(Self { header, frames })
+    }
+}
+
+/// A minimal virtual terminal screen, for replaying output events.
+struct Screen {
+    width: usize,
+    height: usize,
+    cells: Vec<Styled>,
+    cursor: (usize, usize),
+    style: Style,
+}
+
+impl Screen {
+    fn new(width: usize, height: usize) -> Self {
+        Self {
+            width,
+            height,
+            cells: vec![Styled::from(' '); width * height],
+            cursor: (0, 0),
+            style: Style::new(),
+        }
+    }
+
+    fn snapshot(&self) -> Grid<Styled> {
+        Grid::from_cells(self.width, self.height, self.cells.clone())
+    }
+
+    /// Erases the cells in the given range of (row-major) indices.
+    fn erase(&mut self, from: usize, to: usize) {
+        let to = to.min(self.cells.len());
+        if from < to {
+            self.cells[from..to].fill(Styled::from(' '));
+        }
+    }
+
+    /// Moves the cursor to the next row, scrolling the screen up if it is on the last row.
+    fn line_feed(&mut self) {
+        if self.cursor.1 + 1 < self.height {
+            self.cursor.1 += 1;
+        } else if self.height > 0 {
+            self.cells.rotate_left(self.width);
+            let last = self.width * (self.height - 1);
+            self.erase(last, self.cells.len());
+        }
+    }
+
+    fn write(&mut self, data: &str) {
+        let mut chars = data.chars().peekable();
+        while let Some(c) = chars.next() {
+            match c {
+                '\x1b' => match chars.next() {
+                    Some('[') => {
+                        let mut parameters = String::new();
+                        for c in chars.by_ref() {
+                            if ('@'..='~').contains(&c) {
+                                self.control(c, &parameters);
+                                break;
+                            }
+                            parameters.push(c);
+                        }
+                    }
+                    Some(']') => {
+                        while let Some(c) = chars.next() {
+                            if c == '\x07' || (c == '\x1b' && chars.next_if_eq(&'\\').is_some()) {
+                                break;
+                            }
+                        }
+                    }
+                    _ => {}
+                },
+                '\r' => self.cursor.0 = 0,
+                '\n' => self.line_feed(),
+                '\x08' => self.cursor.0 = self.cursor.0.saturating_sub(1),
+                c if c.is_control() => {}
+                c => {
+                    if self.width == 0 || self.height == 0 {
+                        continue;
+                    }
+                    if self.cursor.0 >= self.width {
+                        self.cursor.0 = 0;
+                        self.line_feed();
+                    }
+                    let (x, y) = self.cursor;
+                    self.cells[y * self.width + x] = Styled::new(c, self.style);
+                    self.cursor.0 += 1;
+                }
+            }
+        }
+    }
+
+    /// Applies a control sequence (`ESC [ parameters final`).
+    fn control(&mut self, command: char, parameters: &str) {
+        let mut numbers = parameters
+            .split(';')
+            .map(|n| n.parse::<usize>().unwrap_or(0));
+        let mut next = |default: usize| numbers.next().filter(|&n| n != 0).unwrap_or(default);
+        let (x, y) = self.cursor;
+        let index = y * self.width + x.min(self.width);
+        match command {
+            'm' => self.style.apply_sgr(parameters),
+            'H' | 'f' => {
+                let row = next(1) - 1;
+                let column = next(1) - 1;
+                self.cursor = (
+                    column.min(self.width.saturating_sub(1)),
+                    row.min(self.height.saturating_sub(1)),
+                );
+            }
+            'A' => self.cursor.1 = y.saturating_sub(next(1)),
+            'B' => self.cursor.1 = (y + next(1)).min(self.height.saturating_sub(1)),
+            'C' => self.cursor.0 = (x + next(1)).min(self.width.saturating_sub(1)),
+            'D' => self.cursor.0 = x.saturating_sub(next(1)),
+            'J' => match parameters {
+                "" | "0" => self.erase(index, self.cells.len()),
+                "1" => self.erase(0, index + 1),
+                _ => self.erase(0, self.cells.len()),
+            },
+            'K' => match parameters {
+                "" | "0" => self.erase(index, (y + 1) * self.width),
+                "1" => self.erase(y * self.width, index + 1),
+                _ => self.erase(y * self.width, (y + 1) * self.width),
+            },
+            _ => {}
+        }
+    }
+}
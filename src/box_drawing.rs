@@ -0,0 +1,223 @@
+//! Box-drawing characters (`U+2500` to `U+257F`), decoded into the lines they are made of.
+//!
+//! Used to draw box-drawing characters as crisp shapes (rather than as glyphs of a font) when
+//! exporting grids, e.g. as SVG, and to find the junctions that join the lines of a border, e.g.
+//! in [`Table`][`crate::art::Table`].
+
+/// The weight of a line in a box-drawing character.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Weight {
+    Light,
+    Heavy,
+    Double,
+}
+
+/// The lines of a box-drawing character, from the center of the cell to each edge.
+///
+/// In order: up, right, down, left.
+pub(crate) type Arms = [Option<Weight>; 4];
+
+const X: Option<Weight> = None;
+const L: Option<Weight> = Some(Weight::Light);
+const H: Option<Weight> = Some(Weight::Heavy);
+const D: Option<Weight> = Some(Weight::Double);
+
+/// Every box-drawing character made of straight lines (i.e. not dashed or diagonal).
+///
+/// Rounded corners are decoded as their square equivalent.
+#[rustfmt::skip]
+const TABLE: [(char, Arms); 113] = [
+    ('─', [X, L, X, L]),
+    ('━', [X, H, X, H]),
+    ('│', [L, X, L, X]),
+    ('┃', [H, X, H, X]),
+    ('┌', [X, L, L, X]),
+    ('┍', [X, H, L, X]),
+    ('┎', [X, L, H, X]),
+    ('┏', [X, H, H, X]),
+    ('┐', [X, X, L, L]),
+    ('┑', [X, X, L, H]),
+    ('┒', [X, X, H, L]),
+    ('┓', [X, X, H, H]),
+    ('└', [L, L, X, X]),
+    ('┕', [L, H, X, X]),
+    ('┖', [H, L, X, X]),
+    ('┗', [H, H, X, X]),
+    ('┘', [L, X, X, L]),
+    ('┙', [L, X, X, H]),
+    ('┚', [H, X, X, L]),
+    ('┛', [H, X, X, H]),
+    ('├', [L, L, L, X]),
+    ('┝', [L, H, L, X]),
+    ('┞', [H, L, L, X]),
+    ('┟', [L, L, H, X]),
+    ('┠', [H, L, H, X]),
+    ('┡', [H, H, L, X]),
+    ('┢', [L, H, H, X]),
+    ('┣', [H, H, H, X]),
+    ('┤', [L, X, L, L]),
+    ('┥', [L, X, L, H]),
+    ('┦', [H, X, L, L]),
+    ('┧', [L, X, H, L]),
+    ('┨', [H, X, H, L]),
+    ('┩', [H, X, L, H]),
+    ('┪', [L, X, H, H]),
+    ('┫', [H, X, H, H]),
+    ('┬', [X, L, L, L]),
+    ('┭', [X, L, L, H]),
+    ('┮', [X, H, L, L]),
+    ('┯', [X, H, L, H]),
+    ('┰', [X, L, H, L]),
+    ('┱', [X, L, H, H]),
+    ('┲', [X, H, H, L]),
+    ('┳', [X, H, H, H]),
+    ('┴', [L, L, X, L]),
+    ('┵', [L, L, X, H]),
+    ('┶', [L, H, X, L]),
+    ('┷', [L, H, X, H]),
+    ('┸', [H, L, X, L]),
+    ('┹', [H, L, X, H]),
+    ('┺', [H, H, X, L]),
+    ('┻', [H, H, X, H]),
+    ('┼', [L, L, L, L]),
+    ('┽', [L, L, L, H]),
+    ('┾', [L, H, L, L]),
+    ('┿', [L, H, L, H]),
+    ('╀', [H, L, L, L]),
+    ('╁', [L, L, H, L]),
+    ('╂', [H, L, H, L]),
+    ('╃', [H, L, L, H]),
+    ('╄', [H, H, L, L]),
+    ('╅', [L, L, H, H]),
+    ('╆', [L, H, H, L]),
+    ('╇', [H, H, L, H]),
+    ('╈', [L, H, H, H]),
+    ('╉', [H, L, H, H]),
+    ('╊', [H, H, H, L]),
+    ('╋', [H, H, H, H]),
+    ('═', [X, D, X, D]),
+    ('║', [D, X, D, X]),
+    ('╒', [X, D, L, X]),
+    ('╓', [X, L, D, X]),
+    ('╔', [X, D, D, X]),
+    ('╕', [X, X, L, D]),
+    ('╖', [X, X, D, L]),
+    ('╗', [X, X, D, D]),
+    ('╘', [L, D, X, X]),
+    ('╙', [D, L, X, X]),
+    ('╚', [D, D, X, X]),
+    ('╛', [L, X, X, D]),
+    ('╜', [D, X, X, L]),
+    ('╝', [D, X, X, D]),
+    ('╞', [L, D, L, X]),
+    ('╟', [D, L, D, X]),
+    ('╠', [D, D, D, X]),
+    ('╡', [L, X, L, D]),
+    ('╢', [D, X, D, L]),
+    ('╣', [D, X, D, D]),
+    ('╤', [X, D, L, D]),
+    ('╥', [X, L, D, L]),
+    ('╦', [X, D, D, D]),
+    ('╧', [L, D, X, D]),
+    ('╨', [D, L, X, L]),
+    ('╩', [D, D, X, D]),
+    ('╪', [L, D, L, D]),
+    ('╫', [D, L, D, L]),
+    ('╬', [D, D, D, D]),
+    ('╭', [X, L, L, X]),
+    ('╮', [X, X, L, L]),
+    ('╯', [L, X, X, L]),
+    ('╰', [L, L, X, X]),
+    ('╴', [X, X, X, L]),
+    ('╵', [L, X, X, X]),
+    ('╶', [X, L, X, X]),
+    ('╷', [X, X, L, X]),
+    ('╸', [X, X, X, H]),
+    ('╹', [H, X, X, X]),
+    ('╺', [X, H, X, X]),
+    ('╻', [X, X, H, X]),
+    ('╼', [X, H, X, L]),
+    ('╽', [L, X, H, X]),
+    ('╾', [X, L, X, H]),
+    ('╿', [H, X, L, X]),
+];
+
+/// Returns the lines that the given character is made of, or `None` if it isn't box-drawing.
+pub(crate) fn decode(c: char) -> Option<Arms> {
+    TABLE
+        .iter()
+        .find(|(glyph, _)| *glyph == c)
+        .map(|(_, arms)| *arms)
+}
+
+/// Returns the character that is made of the given lines, or `None` if there isn't one.
+///
+/// Corners are encoded as square rather than rounded.
+pub(crate) fn encode(arms: Arms) -> Option<char> {
+    TABLE
+        .iter()
+        .find(|(_, glyph_arms)| *glyph_arms == arms)
+        .map(|(glyph, _)| *glyph)
+}
+
+/// Returns the rectangles, as `(x, y, width, height)`, that draw the given lines in a cell.
+#[cfg(feature = "std")]
+pub(crate) fn segments(arms: Arms, width: f32, height: f32) -> Vec<(f32, f32, f32, f32)> {
+    let [up, right, down, left] = arms;
+    let thin = (width.min(height) / 8.0).max(1.0);
+    let (cx, cy) = (width / 2.0, height / 2.0);
+    let mut rects = Vec::new();
+
+    // Each arm is drawn as one (or two, if double) strokes from the center to an edge. Strokes
+    // start slightly before the center so that they join the strokes of perpendicular arms.
+    for (weight, edge, vertical) in [
+        (up, 0.0, true),
+        (right, width, false),
+        (down, height, true),
+        (left, 0.0, false),
+    ] {
+        let Some(weight) = weight else {
+            continue;
+        };
+        let (center, cross_center, sides) = if vertical {
+            (cy, cx, [left, right])
+        } else {
+            (cx, cy, [up, down])
+        };
+        let toward = if edge > center { 1.0 } else { -1.0 };
+
+        // Each stroke is `(offset from the center line, thickness, extent past the center)`.
+        let gap = thin;
+        let lines = match weight {
+            Weight::Light => vec![(0.0, thin, thin / 2.0)],
+            Weight::Heavy => vec![(0.0, thin * 2.0, thin)],
+            Weight::Double => sides
+                .iter()
+                .zip([-gap, gap])
+                .map(|(side, offset)| {
+                    // The stroke on the side of a perpendicular arm stops short of the center,
+                    // leaving a passage for that arm; otherwise it extends past the center.
+                    let extent = if side.is_some() { -gap } else { gap };
+                    (offset, thin, extent + thin / 2.0)
+                })
+                .collect(),
+        };
+
+        for (offset, thickness, extent) in lines {
+            let start = center - toward * extent;
+            let (from, to) = if start < edge {
+                (start, edge)
+            } else {
+                (edge, start)
+            };
+            let across = cross_center + offset - thickness / 2.0;
+            if vertical {
+                rects.push((across, from, thickness, to - from));
+            } else {
+                rects.push((from, across, to - from, thickness));
+            }
+        }
+    }
+
+    rects
+}
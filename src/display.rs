@@ -0,0 +1,249 @@
+//! Configure how grids are written as text, e.g. line endings and trimming.
+//!
+//! [`grux::display`][`crate::display`] provides:
+//! - Options for [`DisplayGrid::write_with`][`crate::DisplayGrid::write_with`]: [`DisplayOptions`].
+//! - The line ending written after each row: [`LineEnding`].
+//! - An adapter to use a grid with [`core::fmt::Display`]: [`Display`].
+//!
+//! The defaults match [`DisplayGrid::write_to`][`crate::DisplayGrid::write_to`], i.e. every row,
+//! including the last, is followed by `\n`, and rows are written as-is.
+//!
+//! [`LineEnding`] and [`Display`] only require `core`, and [`DisplayOptions`] requires the `std`
+//! feature.
+
+use core::fmt;
+#[cfg(feature = "std")]
+use core::fmt::Write as _;
+#[cfg(feature = "std")]
+use std::io;
+
+use crate::FormatGrid;
+
+/// The line ending written after each row.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum LineEnding {
+    /// A line feed, i.e. `\n`.
+    #[default]
+    Lf,
+
+    /// A carriage return and a line feed, i.e. `\r\n`.
+    CrLf,
+}
+
+impl LineEnding {
+    /// Returns the line ending as a string.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            LineEnding::Lf => "\n",
+            LineEnding::CrLf => "\r\n",
+        }
+    }
+}
+
+/// Options for writing a grid as text.
+///
+/// # Examples
+///
+/// ```
+/// # use grux::DisplayGrid;
+/// # use grux::display::{DisplayOptions, LineEnding};
+/// let grid = vec![vec!['a', ' '], vec!['b']];
+/// let options = DisplayOptions {
+///     line_ending: LineEnding::CrLf,
+///     trailing_newline: false,
+///     trim_trailing: true,
+///     row_prefix: "> ".to_string(),
+///     ..DisplayOptions::default()
+/// };
+///
+/// let mut output = Vec::new();
+/// grid.write_with(&mut output, &options).unwrap();
+///
+/// assert_eq!(output, b"> a\r\n> b");
+/// ```
+#[cfg(feature = "std")]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DisplayOptions {
+    /// The line ending written after each row; defaults to [`LineEnding::Lf`].
+    pub line_ending: LineEnding,
+
+    /// Whether the line ending is also written after the last row; defaults to `true`.
+    pub trailing_newline: bool,
+
+    /// Whether blank cells (i.e. cells that display as whitespace) at the end of each row are
+    /// omitted; defaults to `false`.
+    ///
+    /// Trimming is applied after padding.
+    pub trim_trailing: bool,
+
+    /// If set, rows that are shorter than the widest row (e.g. of a ragged nested vector) are
+    /// padded with this character; defaults to `None`.
+    pub pad: Option<char>,
+
+    /// Written before each row; defaults to an empty string.
+    pub row_prefix: String,
+
+    /// Written after each row, before the line ending; defaults to an empty string.
+    pub row_suffix: String,
+}
+
+#[cfg(feature = "std")]
+impl Default for DisplayOptions {
+    fn default() -> Self {
+        Self {
+            line_ending: LineEnding::Lf,
+            trailing_newline: true,
+            trim_trailing: false,
+            pad: None,
+            row_prefix: String::new(),
+            row_suffix: String::new(),
+        }
+    }
+}
+
+/// Displays a grid with [`core::fmt::Display`], e.g. in `format!` or `println!`.
+///
+/// Created by [`FormatGrid::display`]; the grid is written with [`FormatGrid::write_fmt_to`],
+/// i.e. without an intermediate buffer of bytes.
+///
+/// # Examples
+///
+/// ```
+/// # use grux::FormatGrid;
+/// let grid = [['a', 'b'], ['c', 'd']];
+///
+/// assert_eq!(format!("grid:\n{}", grid.display()), "grid:\nab\ncd\n");
+/// ```
+pub struct Display<'a, G: ?Sized> {
+    grid: &'a G,
+}
+
+impl<'a, G: ?Sized> Display<'a, G> {
+    pub(crate) fn new(grid: &'a G) -> Self {
+        Self { grid }
+    }
+}
+
+impl<G: FormatGrid + ?Sized> fmt::Display for Display<'_, G> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.grid.write_fmt_to(f)
+    }
+}
+
+impl<G: FormatGrid + ?Sized> fmt::Debug for Display<'_, G> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+/// Writes rows of cells into an output stream; see [`format_rows`].
+#[cfg(feature = "std")]
+pub(crate) fn write_rows<R>(
+    stream: &mut impl io::Write,
+    rows: impl IntoIterator<Item = R>,
+    width: usize,
+    options: &DisplayOptions,
+) -> io::Result<()>
+where
+    R: IntoIterator,
+    R::Item: fmt::Display,
+{
+    /// Adapts an output stream to [`fmt::Write`], keeping the first error.
+    struct Adapter<'a, W> {
+        stream: &'a mut W,
+        error: io::Result<()>,
+    }
+
+    impl<W: io::Write> fmt::Write for Adapter<'_, W> {
+        fn write_str(&mut self, s: &str) -> fmt::Result {
+            self.stream.write_all(s.as_bytes()).map_err(|error| {
+                self.error = Err(error);
+                fmt::Error
+            })
+        }
+    }
+
+    let mut adapter = Adapter {
+        stream,
+        error: Ok(()),
+    };
+    match format_rows(&mut adapter, rows, width, options) {
+        Ok(()) => Ok(()),
+        Err(error) => adapter.error.and(Err(io::Error::other(error))),
+    }
+}
+
+/// Formats rows of cells, as wide as the widest row, according to the options.
+///
+/// This is the shared implementation of `write_with` for the built-in grids, so that they all
+/// format rows the same way.
+#[cfg(feature = "std")]
+pub(crate) fn format_rows<R>(
+    out: &mut impl fmt::Write,
+    rows: impl IntoIterator<Item = R>,
+    width: usize,
+    options: &DisplayOptions,
+) -> fmt::Result
+where
+    R: IntoIterator,
+    R::Item: fmt::Display,
+{
+    let mut rows = rows.into_iter().peekable();
+    let mut line = String::new();
+    while let Some(row) = rows.next() {
+        line.clear();
+        line.push_str(&options.row_prefix);
+
+        // The end of the last non-blank cell, for trimming.
+        let mut end = line.len();
+        let mut count = 0;
+        for cell in row {
+            let start = line.len();
+            write!(line, "{}", cell)?;
+            if !line[start..].chars().all(char::is_whitespace) {
+                end = line.len();
+            }
+            count += 1;
+        }
+        if let Some(pad) = options.pad {
+            for _ in count..width {
+                line.push(pad);
+                if !pad.is_whitespace() {
+                    end = line.len();
+                }
+            }
+        }
+        if options.trim_trailing {
+            line.truncate(end);
+        }
+
+        line.push_str(&options.row_suffix);
+        if options.trailing_newline || rows.peek().is_some() {
+            line.push_str(options.line_ending.as_str());
+        }
+        out.write_str(&line)?;
+    }
+    Ok(())
+}
+
+/// Formats rows of cells, each followed by `\n`, i.e. the same as [`format_rows`] with the default
+/// options, but without allocating.
+///
+/// This is the shared implementation of [`FormatGrid::write_fmt_to`] for the built-in grids.
+pub(crate) fn format_plain<R>(
+    out: &mut impl fmt::Write,
+    rows: impl IntoIterator<Item = R>,
+) -> fmt::Result
+where
+    R: IntoIterator,
+    R::Item: fmt::Display,
+{
+    for row in rows {
+        for cell in row {
+            write!(out, "{}", cell)?;
+        }
+        out.write_char('\n')?;
+    }
+    Ok(())
+}
@@ -0,0 +1,808 @@
+//! Additional grid data structures, beyond the built-in implementations for arrays and vectors.
+//!
+//! [`grux::grid`][`crate::grid`] provides:
+//! - A fixed-size grid backed by a single, flat vector: [`Grid`].
+//! - An unbounded grid keyed by signed coordinates: [`SparseGrid`].
+//! - A grid of fixed-size chunks that are allocated and (un)loaded on demand: [`ChunkedGrid`].
+//!
+//! This module requires the `alloc` feature, and [`SparseGrid`] and [`ChunkedGrid`] also require
+//! the `std` feature.
+//!
+//! With the `serde` feature, [`Grid`] can be serialized and deserialized, either element by
+//! element, or (for a grid of characters) compactly as rows of strings with `grid::compact`.
+
+use alloc::{vec, vec::Vec};
+use core::fmt::Display;
+#[cfg(feature = "std")]
+use std::collections::HashMap;
+
+use crate::{display, FormatGrid, GridReader, GridWriter};
+#[cfg(feature = "std")]
+use crate::{display::DisplayOptions, DisplayGrid};
+
+/// A fixed-size grid, stored as a single row-major vector of cells.
+///
+/// Unlike a nested vector, a grid is always rectangular; unlike a nested array, its size does not
+/// need to be known at compile time.
+///
+/// # Examples
+///
+/// ```
+/// # #[cfg(feature = "std")]
+/// # {
+/// # use grux::{DisplayGrid, GridWriter};
+/// # use grux::grid::Grid;
+/// let mut grid = Grid::new(3, 2, '.');
+///
+/// grid.set((1, 1), 'X');
+///
+/// assert_eq!(grid.size(), (3, 2));
+/// assert_eq!(grid.to_string().unwrap(), "...\n.X.\n");
+/// # }
+/// ```
+///
+/// # Serialization
+///
+/// With the `serde` feature, a grid is serialized as its `width`, `height`, and row-major `cells`;
+/// deserializing fails if the number of cells is not `width * height`. See `grid::compact` for a
+/// more compact encoding of a grid of characters.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Creates a grid of the given size, with every cell set to the given element.
+    #[must_use]
+    pub fn new(width: usize, height: usize, element: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            width,
+            height,
+            cells: vec![element; width * height],
+        }
+    }
+
+    /// Creates a grid of the given size from row-major cells.
+    ///
+    /// # Panics
+    ///
+    /// If the number of cells is not `width * height`.
+    #[must_use]
+    pub fn from_cells(width: usize, height: usize, cells: Vec<T>) -> Self {
+        assert_eq!(
+            cells.len(),
+            width * height,
+            "Grid must contain exactly width * height cells"
+        );
+        Self {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    /// Returns the `(width, height)` of the grid.
+    #[must_use]
+    pub fn size(&self) -> (usize, usize) {
+        (self.width, self.height)
+    }
+
+    /// Returns the row-major cells of the grid.
+    #[must_use]
+    pub fn cells(&self) -> &[T] {
+        &self.cells
+    }
+
+    /// Returns an iterator over the rows of the grid.
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        (0..self.height).map(|y| &self.cells[y * self.width..(y + 1) * self.width])
+    }
+
+    /// Unwraps the row-major cells of the grid.
+    #[must_use]
+    pub fn into_cells(self) -> Vec<T> {
+        self.cells
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T> serde::Deserialize<'de> for Grid<T>
+where
+    T: serde::Deserialize<'de>,
+{
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(serde::Deserialize)]
+        #[serde(rename = "Grid")]
+        struct Fields<T> {
+            width: usize,
+            height: usize,
+            cells: Vec<T>,
+        }
+
+        let Fields {
+            width,
+            height,
+            cells,
+        } = Fields::deserialize(deserializer)?;
+        if width.checked_mul(height) != Some(cells.len()) {
+            return Err(serde::de::Error::custom(
+                "grid must contain exactly width * height cells",
+            ));
+        }
+        Ok(Self::from_cells(width, height, cells))
+    }
+}
+
+/// A compact encoding of a grid of characters, as its dimensions and rows of strings.
+///
+/// Use with `#[serde(with = "grux::grid::compact")]` on a [`Grid<char>`] field; requires the
+/// `serde` feature.
+///
+/// # Examples
+///
+/// ```
+/// # use grux::grid::Grid;
+/// #[derive(serde::Serialize, serde::Deserialize)]
+/// struct Level {
+///     #[serde(with = "grux::grid::compact")]
+///     map: Grid<char>,
+/// }
+///
+/// let level = Level { map: "#.#\n...".parse().unwrap() };
+/// let json = serde_json::to_string(&level).unwrap();
+///
+/// assert_eq!(json, r##"{"map":{"width":3,"height":2,"rows":["#.#","..."]}}"##);
+/// ```
+#[cfg(feature = "serde")]
+pub mod compact {
+    use alloc::{string::String, vec::Vec};
+
+    use serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
+
+    use super::Grid;
+
+    #[derive(Serialize, Deserialize)]
+    #[serde(rename = "Grid")]
+    struct Compact {
+        width: usize,
+        height: usize,
+        rows: Vec<String>,
+    }
+
+    /// Serializes a grid of characters as its dimensions and rows of strings.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the serializer returns an error.
+    pub fn serialize<S: Serializer>(grid: &Grid<char>, serializer: S) -> Result<S::Ok, S::Error> {
+        Compact {
+            width: grid.width,
+            height: grid.height,
+            rows: grid.rows().map(|row| row.iter().collect()).collect(),
+        }
+        .serialize(serializer)
+    }
+
+    /// Deserializes a grid of characters from its dimensions and rows of strings.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the deserializer returns an error, or if the rows do not match the
+    /// dimensions.
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Grid<char>, D::Error> {
+        let Compact {
+            width,
+            height,
+            rows,
+        } = Compact::deserialize(deserializer)?;
+        if rows.len() != height {
+            return Err(D::Error::custom(format_args!(
+                "expected {} rows, got {}",
+                height,
+                rows.len()
+            )));
+        }
+
+        // The dimensions are untrusted, so cells are only added as the rows are validated.
+        let mut cells = Vec::new();
+        for (i, row) in rows.iter().enumerate() {
+            let length = row.chars().count();
+            if length != width {
+                return Err(D::Error::custom(format_args!(
+                    "expected row {} to be {} characters long, got {}",
+                    i, width, length
+                )));
+            }
+            cells.extend(row.chars());
+        }
+        if width.checked_mul(height).is_none() {
+            return Err(D::Error::custom("grid dimensions overflow"));
+        }
+        Ok(Grid::from_cells(width, height, cells))
+    }
+}
+
+/// Provides [`GridWriter`] for a flat grid.
+impl<T> GridWriter for Grid<T>
+where
+    T: Display,
+{
+    type Element = T;
+
+    /// Sets the element at the given `(x, y)` position.
+    ///
+    /// # Panics
+    ///
+    /// If the position is out of bounds.
+    fn set(&mut self, position: (usize, usize), element: Self::Element) {
+        let (x, y) = position;
+        assert!(x < self.width && y < self.height, "Position out of bounds");
+        self.cells[y * self.width + x] = element;
+    }
+}
+
+/// Provides [`GridReader`] for a flat grid.
+impl<T> GridReader for Grid<T> {
+    type Element = T;
+
+    fn get(&self, position: (usize, usize)) -> Option<&Self::Element> {
+        let (x, y) = position;
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+        self.cells.get(y * self.width + x)
+    }
+}
+
+/// Provides [`DisplayGrid`] for a flat grid.
+#[cfg(feature = "std")]
+impl<T> DisplayGrid for Grid<T>
+where
+    T: Display,
+{
+    fn write_to(&self, stream: &mut impl std::io::Write) -> std::io::Result<()> {
+        self.write_with(stream, &DisplayOptions::default())
+    }
+
+    fn write_with(
+        &self,
+        stream: &mut impl std::io::Write,
+        options: &DisplayOptions,
+    ) -> std::io::Result<()> {
+        display::write_rows(stream, self.rows(), self.width, options)
+    }
+}
+
+/// Provides [`FormatGrid`] for a flat grid.
+impl<T> FormatGrid for Grid<T>
+where
+    T: Display,
+{
+    fn write_fmt_to(&self, out: &mut impl core::fmt::Write) -> core::fmt::Result {
+        display::format_plain(out, self.rows())
+    }
+}
+
+/// A sparse, unbounded grid backed by a hash map and keyed by signed `(x, y)` coordinates.
+///
+/// Unlike a growable nested vector, only the cells that have been written to are stored, and the
+/// grid can extend infinitely in every direction (including negative coordinates). Cells that were
+/// never written to (i.e. "holes") are displayed as a configurable default element.
+///
+/// When displayed, only the bounding box of the occupied cells is written.
+///
+/// # Examples
+///
+/// ```
+/// # use grux::DisplayGrid;
+/// # use grux::grid::SparseGrid;
+/// let mut grid = SparseGrid::new('.');
+///
+/// grid.insert((-1, -1), 'A');
+/// grid.insert((1, 0), 'B');
+///
+/// assert_eq!(grid.bounds(), Some(((-1, -1), (1, 0))));
+/// assert_eq!(grid.to_string().unwrap(), "A..\n..B\n");
+/// ```
+#[cfg(feature = "std")]
+#[derive(Clone, Debug)]
+pub struct SparseGrid<T> {
+    cells: HashMap<(isize, isize), T>,
+    default: T,
+    origin: (isize, isize),
+    bounds: Option<((isize, isize), (isize, isize))>,
+}
+
+#[cfg(feature = "std")]
+impl<T> SparseGrid<T> {
+    /// Creates an empty grid, where holes are displayed as the given default element.
+    #[must_use]
+    pub fn new(default: T) -> Self {
+        Self {
+            cells: HashMap::new(),
+            default,
+            origin: (0, 0),
+            bounds: None,
+        }
+    }
+
+    /// Returns the element displayed for cells that were never written to.
+    #[must_use]
+    pub fn default_element(&self) -> &T {
+        &self.default
+    }
+
+    /// Returns the signed position that [`GridWriter::set`] treats as `(0, 0)`.
+    #[must_use]
+    pub fn origin(&self) -> (isize, isize) {
+        self.origin
+    }
+
+    /// Sets the signed position that [`GridWriter::set`] treats as `(0, 0)`.
+    ///
+    /// This makes it possible to draw sprites (which are always positioned with unsigned
+    /// coordinates) into the negative parts of the grid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use grux::GridWriter;
+    /// # use grux::grid::SparseGrid;
+    /// let mut grid = SparseGrid::new(' ');
+    /// grid.set_origin((-10, -10));
+    ///
+    /// grid.set((0, 0), 'X');
+    ///
+    /// assert_eq!(grid.get((-10, -10)), Some(&'X'));
+    /// ```
+    pub fn set_origin(&mut self, origin: (isize, isize)) {
+        self.origin = origin;
+    }
+
+    /// Returns the number of occupied cells.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    /// Returns `true` if no cells are occupied.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// Returns the inclusive `(min, max)` corners of the occupied cells, or `None` if empty.
+    #[must_use]
+    pub fn bounds(&self) -> Option<((isize, isize), (isize, isize))> {
+        self.bounds
+    }
+
+    /// Returns the element at the given signed position, or `None` if the cell is a hole.
+    #[must_use]
+    pub fn get(&self, position: (isize, isize)) -> Option<&T> {
+        self.cells.get(&position)
+    }
+
+    /// Returns the element at the given signed position, or the default element if it's a hole.
+    #[must_use]
+    pub fn get_or_default(&self, position: (isize, isize)) -> &T {
+        self.cells.get(&position).unwrap_or(&self.default)
+    }
+
+    /// Sets the element at the given signed position, returning the previous element (if any).
+    pub fn insert(&mut self, position: (isize, isize), element: T) -> Option<T> {
+        let (x, y) = position;
+        self.bounds = Some(match self.bounds {
+            Some(((min_x, min_y), (max_x, max_y))) => {
+                ((min_x.min(x), min_y.min(y)), (max_x.max(x), max_y.max(y)))
+            }
+            None => (position, position),
+        });
+        self.cells.insert(position, element)
+    }
+
+    /// Removes the element at the given signed position, returning it (if any).
+    ///
+    /// The bounding box shrinks if the removed cell was on its edge.
+    pub fn remove(&mut self, position: (isize, isize)) -> Option<T> {
+        let removed = self.cells.remove(&position)?;
+        let (x, y) = position;
+        if let Some(((min_x, min_y), (max_x, max_y))) = self.bounds {
+            if x == min_x || x == max_x || y == min_y || y == max_y {
+                self.recompute_bounds();
+            }
+        }
+        Some(removed)
+    }
+
+    /// Removes all occupied cells.
+    pub fn clear(&mut self) {
+        self.cells.clear();
+        self.bounds = None;
+    }
+
+    /// Returns an iterator over the occupied cells and their signed positions, in arbitrary order.
+    pub fn iter(&self) -> impl Iterator<Item = ((isize, isize), &T)> {
+        self.cells
+            .iter()
+            .map(|(position, element)| (*position, element))
+    }
+
+    fn relative_to_origin(&self, position: (usize, usize)) -> Option<(isize, isize)> {
+        let offset = |origin: isize, n: usize| origin.checked_add(isize::try_from(n).ok()?);
+        Some((
+            offset(self.origin.0, position.0)?,
+            offset(self.origin.1, position.1)?,
+        ))
+    }
+
+    fn recompute_bounds(&mut self) {
+        self.bounds = self.cells.keys().fold(None, |bounds, &(x, y)| {
+            Some(match bounds {
+                Some(((min_x, min_y), (max_x, max_y))) => {
+                    ((x.min(min_x), y.min(min_y)), (x.max(max_x), y.max(max_y)))
+                }
+                None => ((x, y), (x, y)),
+            })
+        });
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: Default> Default for SparseGrid<T> {
+    fn default() -> Self {
+        Self::new(T::default())
+    }
+}
+
+/// Provides [`GridWriter`] for a sparse grid.
+///
+/// Positions are relative to the [origin](SparseGrid::origin), which is `(0, 0)` by default.
+#[cfg(feature = "std")]
+impl<T> GridWriter for SparseGrid<T>
+where
+    T: Display,
+{
+    type Element = T;
+
+    /// Sets the element at the given `(x, y)` position, relative to the origin.
+    ///
+    /// # Panics
+    ///
+    /// If the position relative to the origin does not fit in an `isize`.
+    fn set(&mut self, position: (usize, usize), element: Self::Element) {
+        let position = self
+            .relative_to_origin(position)
+            .expect("Position overflows the sparse grid");
+        self.insert(position, element);
+    }
+}
+
+/// Provides [`GridReader`] for a sparse grid.
+///
+/// Positions are relative to the [origin](SparseGrid::origin), and holes are `None`.
+#[cfg(feature = "std")]
+impl<T> GridReader for SparseGrid<T> {
+    type Element = T;
+
+    fn get(&self, position: (usize, usize)) -> Option<&Self::Element> {
+        self.cells.get(&self.relative_to_origin(position)?)
+    }
+}
+
+/// Provides [`DisplayGrid`] for a sparse grid.
+///
+/// Only the bounding box of the occupied cells is written, with holes written as the default
+/// element. An empty grid writes nothing.
+#[cfg(feature = "std")]
+impl<T> DisplayGrid for SparseGrid<T>
+where
+    T: Display,
+{
+    fn write_to(&self, stream: &mut impl std::io::Write) -> std::io::Result<()> {
+        self.write_with(stream, &DisplayOptions::default())
+    }
+
+    fn write_with(
+        &self,
+        stream: &mut impl std::io::Write,
+        options: &DisplayOptions,
+    ) -> std::io::Result<()> {
+        let (rows, width) = self.display_rows();
+        display::write_rows(stream, rows, width, options)
+    }
+}
+
+/// Provides [`FormatGrid`] for a sparse grid, the same as [`DisplayGrid`].
+#[cfg(feature = "std")]
+impl<T> FormatGrid for SparseGrid<T>
+where
+    T: Display,
+{
+    fn write_fmt_to(&self, out: &mut impl core::fmt::Write) -> core::fmt::Result {
+        display::format_plain(out, self.display_rows().0)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T> SparseGrid<T> {
+    /// Returns the rows of the bounding box, with holes as the default element, and its width.
+    fn display_rows(&self) -> (impl Iterator<Item = impl Iterator<Item = &T>>, usize) {
+        // An empty grid has no bounding box, i.e. no rows.
+        let ((min_x, min_y), (max_x, max_y)) = self.bounds.unwrap_or(((0, 0), (-1, -1)));
+        let rows =
+            (min_y..=max_y).map(move |y| (min_x..=max_x).map(move |x| self.get_or_default((x, y))));
+        (rows, max_x.abs_diff(min_x) + 1)
+    }
+}
+
+/// A callback that provides the cells of a chunk that is not currently loaded.
+#[cfg(feature = "std")]
+type ChunkLoader<T> = Box<dyn FnMut((usize, usize)) -> Option<Vec<T>>>;
+
+/// A very large grid that stores fixed-size chunks, each allocated on first write.
+///
+/// Chunks are identified by their `(x, y)` chunk coordinates, i.e. the position of a cell divided
+/// by the chunk size. Each chunk is stored as a flat, row-major vector of cells.
+///
+/// Chunks can be unloaded (for example, to persist them to disk) and loaded back again, either
+/// explicitly with [`ChunkedGrid::load`] or on demand through a [loader](ChunkedGrid::with_loader)
+/// that is invoked the first time a chunk that is not in memory is written to.
+///
+/// # Examples
+///
+/// ```
+/// # use grux::GridWriter;
+/// # use grux::art::{FillRect, Sprite};
+/// # use grux::grid::ChunkedGrid;
+/// let mut grid = ChunkedGrid::new(16, 16, ' ');
+///
+/// // Only the chunks that are written to are allocated.
+/// FillRect::new(2, 2, '█').draw_to((1_000_000, 15), &mut grid);
+///
+/// assert_eq!(grid.get((1_000_001, 16)), &'█');
+/// assert_eq!(grid.loaded_chunks().count(), 2);
+/// ```
+#[cfg(feature = "std")]
+pub struct ChunkedGrid<T> {
+    chunk_width: usize,
+    chunk_height: usize,
+    default: T,
+    chunks: HashMap<(usize, usize), Vec<T>>,
+    loader: Option<ChunkLoader<T>>,
+}
+
+#[cfg(feature = "std")]
+impl<T> ChunkedGrid<T> {
+    /// Creates an empty grid with chunks of the given size, where unwritten cells are `default`.
+    ///
+    /// # Panics
+    ///
+    /// If the chunk width or height is zero.
+    #[must_use]
+    pub fn new(chunk_width: usize, chunk_height: usize, default: T) -> Self {
+        assert!(chunk_width > 0, "Chunk width must be at least 1");
+        assert!(chunk_height > 0, "Chunk height must be at least 1");
+        Self {
+            chunk_width,
+            chunk_height,
+            default,
+            chunks: HashMap::new(),
+            loader: None,
+        }
+    }
+
+    /// Sets a callback that is invoked when a chunk that is not loaded is written to.
+    ///
+    /// The loader returns the previously saved cells of the chunk, or `None` to allocate a new
+    /// chunk filled with the default element.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use grux::GridWriter;
+    /// # use grux::grid::ChunkedGrid;
+    /// let mut grid = ChunkedGrid::new(2, 2, '.').with_loader(|chunk| match chunk {
+    ///     (0, 0) => Some(vec!['A', 'B', 'C', 'D']),
+    ///     _ => None,
+    /// });
+    ///
+    /// grid.set((0, 0), 'X');
+    ///
+    /// assert_eq!(grid.get((0, 0)), &'X');
+    /// assert_eq!(grid.get((1, 1)), &'D');
+    /// ```
+    #[must_use]
+    pub fn with_loader(
+        mut self,
+        loader: impl FnMut((usize, usize)) -> Option<Vec<T>> + 'static,
+    ) -> Self {
+        self.loader = Some(Box::new(loader));
+        self
+    }
+
+    /// Returns the `(width, height)` of each chunk.
+    #[must_use]
+    pub fn chunk_size(&self) -> (usize, usize) {
+        (self.chunk_width, self.chunk_height)
+    }
+
+    /// Returns the chunk coordinates that contain the given `(x, y)` position.
+    #[must_use]
+    pub fn chunk_of(&self, position: (usize, usize)) -> (usize, usize) {
+        (
+            position.0 / self.chunk_width,
+            position.1 / self.chunk_height,
+        )
+    }
+
+    /// Returns the element at the given `(x, y)` position.
+    ///
+    /// Only loaded chunks are visible: cells in chunks that are not loaded are returned as the
+    /// default element, without invoking the loader. Use [`ChunkedGrid::get_or_load`] to read
+    /// through the loader instead.
+    #[must_use]
+    pub fn get(&self, position: (usize, usize)) -> &T {
+        self.chunks
+            .get(&self.chunk_of(position))
+            .map_or(&self.default, |cells| &cells[self.index_of(position)])
+    }
+
+    /// Returns the element at the given `(x, y)` position, first loading its chunk if needed.
+    ///
+    /// If the chunk is not loaded, the loader (if any) is invoked, the same as when writing; if it
+    /// returns `None`, the default element is returned without allocating a chunk.
+    ///
+    /// # Panics
+    ///
+    /// If the loader returns a chunk with the wrong number of cells.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use grux::grid::ChunkedGrid;
+    /// let mut grid = ChunkedGrid::new(2, 2, '.').with_loader(|chunk| match chunk {
+    ///     (0, 0) => Some(vec!['A', 'B', 'C', 'D']),
+    ///     _ => None,
+    /// });
+    ///
+    /// assert_eq!(grid.get((1, 1)), &'.');
+    /// assert_eq!(grid.get_or_load((1, 1)), &'D');
+    /// assert!(grid.is_loaded((0, 0)));
+    /// ```
+    pub fn get_or_load(&mut self, position: (usize, usize)) -> &T {
+        let chunk = self.chunk_of(position);
+        if !self.chunks.contains_key(&chunk) {
+            if let Some(cells) = self.loader.as_mut().and_then(|loader| loader(chunk)) {
+                self.load(chunk, cells);
+            }
+        }
+        ChunkedGrid::get(self, position)
+    }
+
+    /// Returns `true` if the chunk at the given chunk coordinates is loaded.
+    #[must_use]
+    pub fn is_loaded(&self, chunk: (usize, usize)) -> bool {
+        self.chunks.contains_key(&chunk)
+    }
+
+    /// Returns an iterator over the chunk coordinates of the loaded chunks, in arbitrary order.
+    pub fn loaded_chunks(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.chunks.keys().copied()
+    }
+
+    /// Loads the given row-major cells as the chunk at the given chunk coordinates.
+    ///
+    /// If the chunk is already loaded, it is replaced.
+    ///
+    /// # Panics
+    ///
+    /// If the number of cells does not match the chunk size.
+    pub fn load(&mut self, chunk: (usize, usize), cells: Vec<T>) {
+        assert_eq!(
+            cells.len(),
+            self.chunk_width * self.chunk_height,
+            "Chunk must contain exactly width * height cells"
+        );
+        self.chunks.insert(chunk, cells);
+    }
+
+    /// Unloads the chunk at the given chunk coordinates, returning its row-major cells (if loaded).
+    pub fn unload(&mut self, chunk: (usize, usize)) -> Option<Vec<T>> {
+        self.chunks.remove(&chunk)
+    }
+
+    /// Unloads every chunk for which `predicate` returns `true`, passing each one to `on_unload`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use grux::GridWriter;
+    /// # use grux::grid::ChunkedGrid;
+    /// let mut grid = ChunkedGrid::new(4, 4, ' ');
+    /// grid.set((0, 0), 'A');
+    /// grid.set((100, 0), 'B');
+    ///
+    /// // Unload everything far away from the origin, e.g. to save it to disk.
+    /// let mut saved = Vec::new();
+    /// grid.unload_where(|(x, _)| x > 10, |chunk, cells| saved.push((chunk, cells)));
+    ///
+    /// assert_eq!(grid.loaded_chunks().collect::<Vec<_>>(), vec![(0, 0)]);
+    /// assert_eq!(saved[0].0, (25, 0));
+    /// ```
+    pub fn unload_where(
+        &mut self,
+        mut predicate: impl FnMut((usize, usize)) -> bool,
+        mut on_unload: impl FnMut((usize, usize), Vec<T>),
+    ) {
+        let unloaded: Vec<_> = self
+            .chunks
+            .keys()
+            .copied()
+            .filter(|chunk| predicate(*chunk))
+            .collect();
+        for chunk in unloaded {
+            if let Some(cells) = self.chunks.remove(&chunk) {
+                on_unload(chunk, cells);
+            }
+        }
+    }
+
+    fn index_of(&self, position: (usize, usize)) -> usize {
+        let x = position.0 % self.chunk_width;
+        let y = position.1 % self.chunk_height;
+        y * self.chunk_width + x
+    }
+}
+
+/// Provides [`GridWriter`] for a chunked grid.
+///
+/// Writing to a chunk that is not loaded first invokes the loader (if any), and otherwise allocates
+/// a new chunk filled with the default element.
+#[cfg(feature = "std")]
+impl<T> GridWriter for ChunkedGrid<T>
+where
+    T: Display + Clone,
+{
+    type Element = T;
+
+    /// Sets the element at the given `(x, y)` position.
+    ///
+    /// # Panics
+    ///
+    /// If the loader returns a chunk with the wrong number of cells.
+    fn set(&mut self, position: (usize, usize), element: Self::Element) {
+        let chunk = self.chunk_of(position);
+        let index = self.index_of(position);
+        if !self.chunks.contains_key(&chunk) {
+            let cells = self
+                .loader
+                .as_mut()
+                .and_then(|loader| loader(chunk))
+                .unwrap_or_else(|| {
+                    vec![self.default.clone(); self.chunk_width * self.chunk_height]
+                });
+            self.load(chunk, cells);
+        }
+        self.chunks.get_mut(&chunk).unwrap()[index] = element;
+    }
+}
+
+/// Provides [`GridReader`] for a chunked grid.
+///
+/// Cells in chunks that are not loaded are read as the default element, and never `None`; like
+/// [`ChunkedGrid::get`], reading never invokes the loader.
+#[cfg(feature = "std")]
+impl<T> GridReader for ChunkedGrid<T> {
+    type Element = T;
+
+    fn get(&self, position: (usize, usize)) -> Option<&Self::Element> {
+        Some(ChunkedGrid::get(self, position))
+    }
+}
@@ -0,0 +1,130 @@
+//! Export grids as HTML, for publishing them in a browser.
+//!
+//! [`grux::html`][`crate::html`] provides:
+//! - Options for [`DisplayGrid::write_html`][`crate::DisplayGrid::write_html`]: [`HtmlOptions`].
+//!
+//! The grid is written as a `<pre>` block, where runs of [styled cells][`crate::style::Styled`]
+//! are written as `<span>` elements with inline CSS.
+
+use std::io;
+
+use crate::style::{self, Color, Style};
+
+/// Options for writing a grid as HTML.
+///
+/// # Examples
+///
+/// ```
+/// # use grux::html::HtmlOptions;
+/// let options = HtmlOptions {
+///     standalone: true,
+///     title: Some("Dashboard".to_string()),
+///     ..HtmlOptions::default()
+/// };
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct HtmlOptions {
+    /// Whether to wrap the `<pre>` block in a complete HTML page.
+    pub standalone: bool,
+
+    /// The `<title>` of the page, if [`HtmlOptions::standalone`].
+    pub title: Option<String>,
+
+    /// The `class` attribute of the `<pre>` block.
+    pub class: Option<String>,
+}
+
+/// The `(foreground, background)` colors assumed when a cell without colors is reversed.
+const DEFAULT_COLORS: (Color, Color) = (Color::Black, Color::BrightWhite);
+
+/// Writes text, which may contain ANSI escape codes, as HTML.
+pub(crate) fn write_html(
+    text: &str,
+    stream: &mut impl io::Write,
+    options: &HtmlOptions,
+) -> io::Result<()> {
+    if options.standalone {
+        writeln!(stream, "<!DOCTYPE html>")?;
+        writeln!(stream, "<html>")?;
+        writeln!(stream, "<head>")?;
+        writeln!(stream, "<meta charset=\"utf-8\">")?;
+        if let Some(title) = &options.title {
+            writeln!(stream, "<title>{}</title>", escape(title))?;
+        }
+        writeln!(stream, "</head>")?;
+        writeln!(stream, "<body>")?;
+    }
+
+    match &options.class {
+        Some(class) => write!(stream, "<pre class=\"{}\">", escape(class))?,
+        None => write!(stream, "<pre>")?,
+    }
+    for (i, line) in style::parse_ansi(text).iter().enumerate() {
+        if i > 0 {
+            writeln!(stream)?;
+        }
+        for (style, text) in line {
+            if style.is_default() {
+                write!(stream, "{}", escape(text))?;
+            } else {
+                write!(
+                    stream,
+                    "<span style=\"{}\">{}</span>",
+                    css(style),
+                    escape(text)
+                )?;
+            }
+        }
+    }
+    writeln!(stream, "</pre>")?;
+
+    if options.standalone {
+        writeln!(stream, "</body>")?;
+        writeln!(stream, "</html>")?;
+    }
+    Ok(())
+}
+
+/// Escapes the characters that have a special meaning in HTML (and XML) text and attributes.
+pub(crate) fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Returns the inline CSS for a style.
+fn css(style: &Style) -> String {
+    let mut declarations = Vec::new();
+    let (foreground, background) = style.colors(DEFAULT_COLORS);
+    if let Some(color) = foreground {
+        declarations.push(format!("color:{}", color.to_hex()));
+    }
+    if let Some(color) = background {
+        declarations.push(format!("background-color:{}", color.to_hex()));
+    }
+    if style.bold {
+        declarations.push("font-weight:bold".to_string());
+    }
+    if style.dim {
+        declarations.push("opacity:0.5".to_string());
+    }
+    if style.italic {
+        declarations.push("font-style:italic".to_string());
+    }
+    match (style.underline, style.strikethrough) {
+        (true, true) => declarations.push("text-decoration:underline line-through".to_string()),
+        (true, false) => declarations.push("text-decoration:underline".to_string()),
+        (false, true) => declarations.push("text-decoration:line-through".to_string()),
+        (false, false) => {}
+    }
+    declarations.join(";")
+}
@@ -0,0 +1,184 @@
+//! A minimal JSON reader and writer, for the line-based formats in this crate (e.g. asciicast).
+
+use std::{fmt::Write, iter::Peekable, str::Chars};
+
+/// A parsed JSON value.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum Value {
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(String),
+    Array(Vec<Value>),
+    Object(Vec<(String, Value)>),
+}
+
+impl Value {
+    /// Returns the value of the given key, if this is an object that contains it.
+    pub(crate) fn get(&self, key: &str) -> Option<&Value> {
+        match self {
+            Value::Object(entries) => entries.iter().find(|(k, _)| k == key).map(|(_, v)| v),
+            _ => None,
+        }
+    }
+
+    /// Returns the number, if this is a number.
+    pub(crate) fn as_f64(&self) -> Option<f64> {
+        match self {
+            Value::Number(n) => Some(*n),
+            _ => None,
+        }
+    }
+
+    /// Returns the string, if this is a string.
+    pub(crate) fn as_str(&self) -> Option<&str> {
+        match self {
+            Value::String(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    /// Returns the elements, if this is an array.
+    pub(crate) fn as_array(&self) -> Option<&[Value]> {
+        match self {
+            Value::Array(values) => Some(values),
+            _ => None,
+        }
+    }
+}
+
+/// The deepest nesting of arrays and objects that [`parse`] accepts.
+///
+/// Parsing is recursive, so without a limit a hostile document could overflow the stack.
+const MAX_DEPTH: usize = 128;
+
+/// Parses a JSON document, or returns `None` if it is malformed or nested too deeply.
+pub(crate) fn parse(text: &str) -> Option<Value> {
+    let mut chars = text.chars().peekable();
+    let value = parse_value(&mut chars, MAX_DEPTH)?;
+    skip_whitespace(&mut chars);
+    chars.next().is_none().then_some(value)
+}
+
+fn skip_whitespace(chars: &mut Peekable<Chars<'_>>) {
+    while chars.next_if(|c| c.is_ascii_whitespace()).is_some() {}
+}
+
+fn parse_value(chars: &mut Peekable<Chars<'_>>, depth: usize) -> Option<Value> {
+    skip_whitespace(chars);
+    match *chars.peek()? {
+        '{' | '[' if depth == 0 => None,
+        '{' => {
+            chars.next();
+            let mut entries = Vec::new();
+            skip_whitespace(chars);
+            if chars.next_if_eq(&'}').is_some() {
+                return Some(Value::Object(entries));
+            }
+            loop {
+                skip_whitespace(chars);
+                let key = parse_string(chars)?;
+                skip_whitespace(chars);
+                chars.next_if_eq(&':')?;
+                entries.push((key, parse_value(chars, depth - 1)?));
+                skip_whitespace(chars);
+                match chars.next()? {
+                    ',' => {}
+                    '}' => return Some(Value::Object(entries)),
+                    _ => return None,
+                }
+            }
+        }
+        '[' => {
+            chars.next();
+            let mut values = Vec::new();
+            skip_whitespace(chars);
+            if chars.next_if_eq(&']').is_some() {
+                return Some(Value::Array(values));
+            }
+            loop {
+                values.push(parse_value(chars, depth - 1)?);
+                skip_whitespace(chars);
+                match chars.next()? {
+                    ',' => {}
+                    ']' => return Some(Value::Array(values)),
+                    _ => return None,
+                }
+            }
+        }
+        '"' => parse_string(chars).map(Value::String),
+        't' => parse_literal(chars, "true", Value::Bool(true)),
+        'f' => parse_literal(chars, "false", Value::Bool(false)),
+        'n' => parse_literal(chars, "null", Value::Null),
+        _ => {
+            let mut number = String::new();
+            while let Some(c) =
+                chars.next_if(|c| matches!(c, '0'..='9' | '-' | '+' | '.' | 'e' | 'E'))
+            {
+                number.push(c);
+            }
+            number.parse().ok().map(Value::Number)
+        }
+    }
+}
+
+fn parse_literal(chars: &mut Peekable<Chars<'_>>, literal: &str, value: Value) -> Option<Value> {
+    for expected in literal.chars() {
+        chars.next_if_eq(&expected)?;
+    }
+    Some(value)
+}
+
+fn parse_string(chars: &mut Peekable<Chars<'_>>) -> Option<String> {
+    chars.next_if_eq(&'"')?;
+    let mut string = String::new();
+    loop {
+        match chars.next()? {
+            '"' => return Some(string),
+            '\\' => match chars.next()? {
+                'b' => string.push('\x08'),
+                'f' => string.push('\x0c'),
+                'n' => string.push('\n'),
+                'r' => string.push('\r'),
+                't' => string.push('\t'),
+                'u' => {
+                    let high = parse_hex(chars)?;
+                    let code = if (0xD800..0xDC00).contains(&high) {
+                        chars.next_if_eq(&'\\')?;
+                        chars.next_if_eq(&'u')?;
+                        let low = parse_hex(chars)?;
+                        0x10000 + ((high - 0xD800) << 10) + low.checked_sub(0xDC00)?
+                    } else {
+                        high
+                    };
+                    string.push(char::from_u32(code)?);
+                }
+                c => string.push(c),
+            },
+            c => string.push(c),
+        }
+    }
+}
+
+fn parse_hex(chars: &mut Peekable<Chars<'_>>) -> Option<u32> {
+    (0..4).try_fold(0, |code, _| Some(code * 16 + chars.next()?.to_digit(16)?))
+}
+
+/// Writes a string as a quoted JSON string.
+pub(crate) fn write_string(out: &mut String, string: &str) {
+    out.push('"');
+    for c in string.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if c.is_control() => {
+                let _ = write!(out, "\\u{:04x}", u32::from(c));
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
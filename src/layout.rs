@@ -0,0 +1,253 @@
+//! Split rectangles into smaller rectangles, e.g. to position the panels of a user interface.
+//!
+//! [`grux::layout`][`crate::layout`] provides:
+//! - How much space a rectangle takes up: [`Constraint`].
+//! - A set of constraints that splits a rectangle horizontally or vertically: [`Layout`].
+//!
+//! The rectangles can be drawn to with [`Viewport`][`crate::adapter::Viewport`], which clips
+//! anything drawn outside of them.
+//!
+//! # Examples
+//!
+//! ```
+//! use grux::Rect;
+//! use grux::adapter::Viewport;
+//! use grux::art::{FillRect, Orientation, Sprite};
+//! use grux::layout::{Constraint, Layout};
+//!
+//! let mut grid = [['.'; 8]; 2];
+//!
+//! // A sidebar of 2 columns, and a main panel that fills the rest.
+//! let layout = Layout::new(Orientation::Horizontal, [Constraint::Length(2), Constraint::Fill(1)])
+//!     .with_spacing(1);
+//! let [sidebar, main] = layout.split(Rect::new(0, 0, 8, 2))[..] else {
+//!     unreachable!()
+//! };
+//! assert_eq!(main, Rect::new(3, 0, 5, 2));
+//!
+//! FillRect::new(9, 9, '#').draw_to((0, 0), &mut Viewport::new(&mut grid, sidebar));
+//! FillRect::new(9, 9, '~').draw_to((0, 0), &mut Viewport::new(&mut grid, main));
+//!
+//! assert_eq!(grid, [
+//!     ['#', '#', '.', '~', '~', '~', '~', '~'],
+//!     ['#', '#', '.', '~', '~', '~', '~', '~'],
+//! ]);
+//! ```
+
+use alloc::{vec, vec::Vec};
+
+use crate::{art::Orientation, Rect};
+
+/// How much space a rectangle of a [`Layout`] takes up, along the direction of the layout.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Constraint {
+    /// Exactly this many cells.
+    Length(usize),
+
+    /// A percentage of the available space, rounded down.
+    Percentage(usize),
+
+    /// A fraction of the available space, i.e. `numerator / denominator`, rounded down.
+    Ratio(usize, usize),
+
+    /// At least this many cells; grows to fill the space left over if there are no
+    /// [`Constraint::Fill`] constraints.
+    Min(usize),
+
+    /// At most this many cells; shrinks first if the constraints do not fit.
+    Max(usize),
+
+    /// A share of the space left over, relative to the other `Fill` constraints.
+    Fill(usize),
+}
+
+/// Splits a rectangle into a row or column of rectangles, according to [`Constraint`]s.
+///
+/// The available space is the size of the rectangle, minus the margins and the spacing between
+/// the rectangles. Each constraint first takes its own length (where `Fill` takes nothing), and
+/// then:
+///
+/// - If there is space left over, it is shared by the `Fill` constraints according to their
+///   weights, or otherwise evenly by the `Min` constraints, or otherwise left empty at the end.
+/// - If the constraints do not fit, the `Max` constraints shrink (the last first), and then the
+///   last rectangles are cut off.
+///
+/// # Examples
+///
+/// ```
+/// # use grux::Rect;
+/// # use grux::art::Orientation;
+/// # use grux::layout::{Constraint, Layout};
+/// let layout = Layout::new(Orientation::Vertical, [
+///     Constraint::Length(1),
+///     Constraint::Percentage(50),
+///     Constraint::Min(1),
+/// ])
+/// .with_margin(1, 1);
+///
+/// assert_eq!(layout.split(Rect::new(0, 0, 10, 12)), vec![
+///     Rect::new(1, 1, 8, 1),
+///     Rect::new(1, 2, 8, 5),
+///     Rect::new(1, 7, 8, 4),
+/// ]);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Layout {
+    orientation: Orientation,
+    constraints: Vec<Constraint>,
+    margin: (usize, usize),
+    spacing: usize,
+}
+
+impl Layout {
+    /// Configures a layout that splits rectangles in the given orientation, i.e. into columns
+    /// that are side by side ([`Orientation::Horizontal`]) or rows that are stacked
+    /// ([`Orientation::Vertical`]).
+    #[must_use]
+    pub fn new(
+        orientation: Orientation,
+        constraints: impl IntoIterator<Item = Constraint>,
+    ) -> Self {
+        Self {
+            orientation,
+            constraints: constraints.into_iter().collect(),
+            margin: (0, 0),
+            spacing: 0,
+        }
+    }
+
+    /// Sets the number of cells left empty on the left and right, and on the top and bottom.
+    #[must_use]
+    pub fn with_margin(mut self, horizontal: usize, vertical: usize) -> Self {
+        self.margin = (horizontal, vertical);
+        self
+    }
+
+    /// Sets the number of cells left empty between the rectangles.
+    #[must_use]
+    pub fn with_spacing(mut self, spacing: usize) -> Self {
+        self.spacing = spacing;
+        self
+    }
+
+    /// Returns the orientation of the layout.
+    #[must_use]
+    pub fn orientation(&self) -> Orientation {
+        self.orientation
+    }
+
+    /// Returns the constraints of the layout.
+    #[must_use]
+    pub fn constraints(&self) -> &[Constraint] {
+        &self.constraints
+    }
+
+    /// Splits the rectangle into one rectangle per constraint, in order.
+    ///
+    /// Rectangles that are cut off are empty, and positioned at the end of the available space.
+    #[must_use]
+    pub fn split(&self, rect: Rect) -> Vec<Rect> {
+        let (mx, my) = self.margin;
+        let inner = Rect::new(
+            rect.x + mx,
+            rect.y + my,
+            rect.width.saturating_sub(mx * 2),
+            rect.height.saturating_sub(my * 2),
+        );
+        let (start, extent) = match self.orientation {
+            Orientation::Horizontal => (inner.x, inner.width),
+            Orientation::Vertical => (inner.y, inner.height),
+        };
+        let gaps = self.spacing * self.constraints.len().saturating_sub(1);
+        let available = extent.saturating_sub(gaps);
+
+        let lengths = self.lengths(available);
+        let mut offset = start;
+        let end = start + extent;
+        let mut rects = Vec::with_capacity(lengths.len());
+        for length in lengths {
+            let length = length.min(end - offset);
+            rects.push(match self.orientation {
+                Orientation::Horizontal => Rect::new(offset, inner.y, length, inner.height),
+                Orientation::Vertical => Rect::new(inner.x, offset, inner.width, length),
+            });
+            offset = (offset + length + self.spacing).min(end);
+        }
+        rects
+    }
+
+    /// Returns the length of each rectangle, out of the available space.
+    fn lengths(&self, available: usize) -> Vec<usize> {
+        let mut lengths: Vec<usize> = self
+            .constraints
+            .iter()
+            .map(|constraint| match *constraint {
+                Constraint::Length(length) | Constraint::Min(length) | Constraint::Max(length) => {
+                    length
+                }
+                Constraint::Percentage(percentage) => available * percentage / 100,
+                Constraint::Ratio(_, 0) | Constraint::Fill(_) => 0,
+                Constraint::Ratio(numerator, denominator) => available * numerator / denominator,
+            })
+            .collect();
+
+        let total: usize = lengths.iter().sum();
+        if total > available {
+            // Shrink `Max` constraints, the last first; anything else is cut off when positioned.
+            let mut excess = total - available;
+            for (length, constraint) in lengths.iter_mut().zip(&self.constraints).rev() {
+                if let Constraint::Max(_) = constraint {
+                    let shrink = excess.min(*length);
+                    *length -= shrink;
+                    excess -= shrink;
+                }
+            }
+            return lengths;
+        }
+
+        let weights: Vec<usize> = self
+            .constraints
+            .iter()
+            .map(|constraint| match constraint {
+                Constraint::Fill(weight) => *weight,
+                _ => 0,
+            })
+            .collect();
+        let weights = if weights.iter().any(|&weight| weight > 0) {
+            weights
+        } else {
+            let is_min = |constraint: &Constraint| matches!(constraint, Constraint::Min(_));
+            self.constraints
+                .iter()
+                .map(|c| usize::from(is_min(c)))
+                .collect()
+        };
+        distribute(&mut lengths, &weights, available - total);
+        lengths
+    }
+}
+
+/// Adds the space to the lengths according to their weights; cells left over from rounding down
+/// go to the first lengths with a weight.
+fn distribute(lengths: &mut [usize], weights: &[usize], space: usize) {
+    let total: usize = weights.iter().sum();
+    if total == 0 {
+        return;
+    }
+    let mut shares = vec![0; lengths.len()];
+    for (share, weight) in shares.iter_mut().zip(weights) {
+        *share = space * weight / total;
+    }
+    let mut left_over = space - shares.iter().sum::<usize>();
+    for (share, weight) in shares.iter_mut().zip(weights) {
+        if left_over > 0 && *weight > 0 {
+            *share += 1;
+            left_over -= 1;
+        }
+    }
+    for (length, share) in lengths.iter_mut().zip(shares) {
+        *length += share;
+    }
+}
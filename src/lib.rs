@@ -3,9 +3,47 @@
 //! [`grux`][`crate`] provides:
 //! - A uniform interface for drawing to a 2D grid: [`GridWriter`].
 //! - A uniform interface for displaying a 2D grid: [`DisplayGrid`].
+//! - A uniform interface for reading from a 2D grid: [`GridReader`].
 //!
 //! The [`grux::art`][`crate::art`] module provides helper types for drawing ASCII art.
 //!
+//! The [`grux::grid`][`crate::grid`] module provides additional grid data structures.
+//!
+//! The [`grux::display`][`crate::display`] module provides options for writing grids as text.
+//!
+//! The [`grux::adapter`][`crate::adapter`] module provides wrappers around existing grids.
+//!
+//! The [`grux::layout`][`crate::layout`] module splits rectangles into panels, which can be drawn to
+//! with [`Viewport`][`crate::adapter::Viewport`].
+//!
+//! The [`grux::style`][`crate::style`] module provides colors and text attributes for cells.
+//!
+//! The [`grux::html`][`crate::html`] and [`grux::svg`][`crate::svg`] modules provide options for
+//! exporting grids as HTML and SVG, and the [`grux::raster`][`crate::raster`] module renders grids
+//! to images.
+//!
+//! The [`grux::text`][`crate::text`] module parses text back into grids, and the
+//! [`grux::asciicast`][`crate::asciicast`] module records and replays animated grids.
+//!
+//! The [`grux::testing`][`crate::testing`] module provides assertions with readable diffs, such
+//! as [`assert_grid_eq!`], and golden snapshots.
+//!
+//! With the `xp` feature, the `grux::xp` module imports and exports REXPaint images.
+//!
+//! # Features
+//!
+//! - `std` (default): [`DisplayGrid`], and the modules that write to output streams, e.g. exports.
+//! - `alloc` (implied by `std`): backends that allocate, e.g. `Vec<Vec<T>>`, `String`, and
+//!   [`grux::grid`][`crate::grid`], and [`grux::layout`][`crate::layout`].
+//! - `png`: writing PNG images, see [`grux::raster`][`crate::raster`].
+//! - `serde`: serializing grids, sprites and styles.
+//! - `xp`: reading and writing REXPaint images, see `grux::xp`.
+//!
+//! Without `std`, the crate is `no_std`: [`GridWriter`], [`GridReader`], the basic
+//! [`art`][`crate::art`] sprites (e.g. [`Line`][`crate::art::Line`]) and fixed-size nested arrays
+//! only require `core`, e.g. to draw to a character LCD on an embedded device. Grids can still be
+//! formatted as text with [`FormatGrid`], e.g. into a fixed-size buffer.
+//!
 //! # Examples
 //!
 //! ## Using a fixed-size nested array
@@ -36,6 +74,8 @@
 //! > - A rectangular grid is not guaranteed
 //!
 //! ```
+//! # #[cfg(feature = "alloc")]
+//! # {
 //! use grux::GridWriter;
 //!
 //! // Create an empty vector (of vectors)
@@ -45,6 +85,7 @@
 //! // This will grow the vector to fit the position, adding empty default vectors as needed.
 //! vec.set((1, 1), 1);
 //! assert_eq!(vec, vec![vec![], vec![0, 1]]);
+//! # }
 //! ```
 //!
 //! ## Using a growable string
@@ -58,6 +99,8 @@
 //! > See [print any grid to a output stream](#print-any-grid-to-a-output-stream) for alternatives.
 //!
 //! ```
+//! # #[cfg(feature = "alloc")]
+//! # {
 //! use grux::GridWriter;
 //!
 //! // Create an empty string.
@@ -67,6 +110,7 @@
 //! // This will grow the string to fit the position, adding empty lines as needed.
 //! string.set((1, 2), '1');
 //! assert_eq!(string, "\n\n 1");
+//! # }
 //! ```
 //!
 //! ## Print any grid to a output stream
@@ -74,6 +118,8 @@
 //! Any type that implements [`DisplayGrid`] can be printed to a output stream _or_ a new string.
 //!
 //! ```
+//! # #[cfg(feature = "std")]
+//! # {
 //! use grux::DisplayGrid;
 //!
 //! // Create a 3x3 array of the letters 'A' - 'I'.
@@ -84,13 +130,53 @@
 //! let string = array.to_string().unwrap();
 //!
 //! assert_eq!(string, "ABC\nDEF\nGHI\n");
+//! # }
 //! ```
 
-use std::{fmt::Display, string::FromUtf8Error};
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
 
+#[cfg(feature = "alloc")]
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::fmt::Display;
+#[cfg(feature = "std")]
+use std::string::FromUtf8Error;
+
+pub mod adapter;
 pub mod art;
+#[cfg(feature = "std")]
+pub mod asciicast;
+#[cfg(feature = "alloc")]
+mod box_drawing;
+pub mod display;
+#[cfg(feature = "std")]
+mod font;
+#[cfg(feature = "alloc")]
+pub mod grid;
+#[cfg(feature = "std")]
+pub mod html;
+#[cfg(feature = "std")]
+mod json;
+#[cfg(feature = "alloc")]
+pub mod layout;
+#[cfg(feature = "std")]
+pub mod raster;
+pub mod style;
+#[cfg(feature = "std")]
+pub mod svg;
+#[cfg(feature = "std")]
+pub mod testing;
+#[cfg(feature = "alloc")]
+pub mod text;
+#[cfg(feature = "xp")]
+pub mod xp;
 
-#[cfg(test)]
+#[cfg(all(test, feature = "std"))]
 mod tests;
 
 /// A trait for a grid-like writable buffer, typically with a fixed width and height.
@@ -130,7 +216,31 @@ pub trait GridWriter {
     fn set(&mut self, position: (usize, usize), element: Self::Element);
 }
 
+/// A trait for a grid-like readable buffer; the counterpart to [`GridWriter`].
+///
+/// The grid is indexed by `(x, y)` coordinates, where `x` is the column and `y` is the row.
+///
+/// # Examples
+///
+/// ```
+/// # use grux::{GridReader, GridWriter};
+/// let mut array = [[0; 2]; 2];
+/// array.set((1, 0), 1);
+///
+/// assert_eq!(array.get((1, 0)), Some(&1));
+/// assert_eq!(array.get((2, 0)), None);
+/// ```
+pub trait GridReader {
+    /// The type of the elements in the grid, e.g. `char`.
+    type Element;
+
+    /// Returns the element at the given `(x, y)` position, or `None` if it is out of bounds.
+    #[must_use]
+    fn get(&self, position: (usize, usize)) -> Option<&Self::Element>;
+}
+
 /// A trait that can be used to display a grid-like buffer to a output stream or a new string.
+#[cfg(feature = "std")]
 pub trait DisplayGrid {
     /// Returns a UTF-8 string representation of the grid.
     ///
@@ -146,6 +256,9 @@ pub trait DisplayGrid {
     ///
     /// ... then it is recommended to use `print` instead (or provide a custom `to_string`).
     ///
+    /// To format the grid without an intermediate buffer of bytes (e.g. with `format!`), use
+    /// [`FormatGrid::display`] instead.
+    ///
     /// # Errors
     ///
     /// Returns an error if the grid contains invalid UTF-8.
@@ -185,6 +298,332 @@ pub trait DisplayGrid {
     /// assert_eq!(output, b"ABC\nDEF\nGHI\n");
     /// ```
     fn write_to(&self, stream: &mut impl std::io::Write) -> std::io::Result<()>;
+
+    /// Writes the grid into the given output stream, formatted with the given options.
+    ///
+    /// With the default options, the output is the same as [`DisplayGrid::write_to`]. The built-in
+    /// grids format their cells directly; other grids are parsed back from the output of
+    /// [`DisplayGrid::write_to`], treating each character (and its [style][`style::Style`]) as a
+    /// cell.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the output stream returns an error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use grux::DisplayGrid;
+    /// # use grux::display::DisplayOptions;
+    /// let grid = vec![vec!['A', 'B', 'C'], vec!['D']];
+    /// let options = DisplayOptions {
+    ///     pad: Some('.'),
+    ///     row_suffix: "|".to_string(),
+    ///     ..DisplayOptions::default()
+    /// };
+    ///
+    /// let mut output = Vec::new();
+    /// grid.write_with(&mut output, &options).unwrap();
+    ///
+    /// assert_eq!(output, b"ABC|\nD..|\n");
+    /// ```
+    fn write_with(
+        &self,
+        stream: &mut impl std::io::Write,
+        options: &display::DisplayOptions,
+    ) -> std::io::Result<()> {
+        let mut output = Vec::new();
+        self.write_to(&mut output)?;
+        let mut lines = style::parse_ansi(&String::from_utf8_lossy(&output));
+        if lines.last().is_some_and(Vec::is_empty) {
+            lines.pop();
+        }
+        let rows: Vec<Vec<style::Styled>> = lines
+            .into_iter()
+            .map(|line| {
+                line.into_iter()
+                    .flat_map(|(style, text)| {
+                        text.chars()
+                            .map(|c| style::Styled::new(c, style))
+                            .collect::<Vec<_>>()
+                    })
+                    .collect()
+            })
+            .collect();
+        let width = rows.iter().map(Vec::len).max().unwrap_or(0);
+        display::write_rows(stream, rows, width, options)
+    }
+
+    /// Writes the grid as HTML, i.e. a `<pre>` block, into the given output stream.
+    ///
+    /// Text is escaped, and runs of cells with the same [style][`style::Styled`] (i.e. written
+    /// with ANSI escape codes) are merged into a `<span>` element. Use
+    /// [`HtmlOptions::standalone`][`html::HtmlOptions::standalone`] to write a complete page.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the output stream returns an error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use grux::DisplayGrid;
+    /// # use grux::html::HtmlOptions;
+    /// # use grux::style::{Color, Style, Styled};
+    /// let red = Style::new().fg(Color::Red);
+    /// let grid = [[Styled::new('<', red), Styled::new('>', red), Styled::from('&')]];
+    ///
+    /// let mut output = Vec::new();
+    /// grid.write_html(&mut output, &HtmlOptions::default()).unwrap();
+    ///
+    /// assert_eq!(
+    ///     String::from_utf8(output).unwrap(),
+    ///     "<pre><span style=\"color:#cd0000\">&lt;&gt;</span>&amp;\n</pre>\n",
+    /// );
+    /// ```
+    fn write_html(
+        &self,
+        stream: &mut impl std::io::Write,
+        options: &html::HtmlOptions,
+    ) -> std::io::Result<()> {
+        let mut output = Vec::new();
+        self.write_to(&mut output)?;
+        html::write_html(&String::from_utf8_lossy(&output), stream, options)
+    }
+
+    /// Writes the grid as an SVG image into the given output stream.
+    ///
+    /// Each cell is laid out at a fixed size (see [`SvgOptions`][`svg::SvgOptions`]), preserving
+    /// the colors and attributes of [styled cells][`style::Styled`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the output stream returns an error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use grux::DisplayGrid;
+    /// # use grux::svg::SvgOptions;
+    /// let grid = [['H', 'i']];
+    ///
+    /// let mut output = Vec::new();
+    /// grid.write_svg(&mut output, &SvgOptions::default()).unwrap();
+    ///
+    /// let svg = String::from_utf8(output).unwrap();
+    /// assert!(svg.contains("width=\"20\" height=\"20\""));
+    /// assert!(svg.contains(">Hi</text>"));
+    /// ```
+    fn write_svg(
+        &self,
+        stream: &mut impl std::io::Write,
+        options: &svg::SvgOptions,
+    ) -> std::io::Result<()> {
+        let mut output = Vec::new();
+        self.write_to(&mut output)?;
+        svg::write_svg(&String::from_utf8_lossy(&output), stream, options)
+    }
+
+    /// Renders the grid into an RGB image, using a built-in bitmap font.
+    ///
+    /// Each cell is [`CELL_WIDTH`][`raster::CELL_WIDTH`] by [`CELL_HEIGHT`][`raster::CELL_HEIGHT`]
+    /// pixels (multiplied by [`RasterOptions::scale`][`raster::RasterOptions::scale`]), preserving
+    /// the colors and attributes of [styled cells][`style::Styled`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if [`DisplayGrid::write_to`] returns an error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use grux::DisplayGrid;
+    /// # use grux::raster::RasterOptions;
+    /// let grid = [['╔', '═', '╗'], ['╚', '═', '╝']];
+    ///
+    /// let image = grid.rasterize(&RasterOptions::default()).unwrap();
+    /// assert_eq!((image.width(), image.height()), (18, 20));
+    ///
+    /// // Write the image as a PPM file (or, with the `png` feature, `write_png`).
+    /// let mut ppm = Vec::new();
+    /// image.write_ppm(&mut ppm).unwrap();
+    /// ```
+    fn rasterize(&self, options: &raster::RasterOptions) -> std::io::Result<raster::RgbImage> {
+        let mut output = Vec::new();
+        self.write_to(&mut output)?;
+        Ok(raster::rasterize(
+            &String::from_utf8_lossy(&output),
+            options,
+        ))
+    }
+}
+
+/// A trait that formats a grid-like buffer as text with [`core::fmt`], e.g. in `format!`.
+///
+/// Unlike [`DisplayGrid`], this trait only requires `core`, so it is also available in `no_std`
+/// builds, and it never goes through a buffer of bytes (i.e. it can't fail on invalid UTF-8).
+///
+/// # Examples
+///
+/// ```
+/// # use grux::FormatGrid;
+/// struct Checkers(usize);
+///
+/// impl FormatGrid for Checkers {
+///     fn write_fmt_to(&self, out: &mut impl core::fmt::Write) -> core::fmt::Result {
+///         for y in 0..self.0 {
+///             for x in 0..self.0 {
+///                 out.write_char(if (x + y) % 2 == 0 { '█' } else { ' ' })?;
+///             }
+///             out.write_char('\n')?;
+///         }
+///         Ok(())
+///     }
+/// }
+///
+/// assert_eq!(format!("{}", Checkers(2).display()), "█ \n █\n");
+/// ```
+pub trait FormatGrid {
+    /// Writes the grid into the given [`core::fmt::Write`], e.g. a [`core::fmt::Formatter`].
+    ///
+    /// Each row is separated by a newline (`\n`), including the last row; for the built-in grids,
+    /// the output is the same as [`DisplayGrid::write_to`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the output returns an error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use grux::FormatGrid;
+    /// let grid = [['A', 'B'], ['C', 'D']];
+    ///
+    /// let mut output = String::new();
+    /// grid.write_fmt_to(&mut output).unwrap();
+    ///
+    /// assert_eq!(output, "AB\nCD\n");
+    /// ```
+    fn write_fmt_to(&self, out: &mut impl core::fmt::Write) -> core::fmt::Result;
+
+    /// Returns an adapter that implements [`core::fmt::Display`], e.g. for use in `format!`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use grux::FormatGrid;
+    /// let grid = [['A', 'B'], ['C', 'D']];
+    ///
+    /// assert_eq!(format!("{}", grid.display()), "AB\nCD\n");
+    /// ```
+    fn display(&self) -> display::Display<'_, Self> {
+        display::Display::new(self)
+    }
+}
+
+/// A rectangular region of a grid, positioned by its top-left `(x, y)` corner.
+///
+/// # Examples
+///
+/// ```
+/// # use grux::Rect;
+/// let rect = Rect::new(1, 2, 3, 4);
+///
+/// assert_eq!(rect.right(), 4);
+/// assert_eq!(rect.bottom(), 6);
+/// assert!(rect.contains((3, 5)));
+/// assert!(!rect.contains((4, 5)));
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Rect {
+    /// The column of the left edge.
+    pub x: usize,
+
+    /// The row of the top edge.
+    pub y: usize,
+
+    /// The number of columns.
+    pub width: usize,
+
+    /// The number of rows.
+    pub height: usize,
+}
+
+impl Rect {
+    /// Creates a rectangle with the given top-left corner and size.
+    #[must_use]
+    pub const fn new(x: usize, y: usize, width: usize, height: usize) -> Self {
+        Self {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
+    /// The column just past the right edge, i.e. `x + width`.
+    #[must_use]
+    pub const fn right(&self) -> usize {
+        self.x + self.width
+    }
+
+    /// The row just past the bottom edge, i.e. `y + height`.
+    #[must_use]
+    pub const fn bottom(&self) -> usize {
+        self.y + self.height
+    }
+
+    /// Returns `true` if the rectangle has no area.
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.width == 0 || self.height == 0
+    }
+
+    /// Returns `true` if the given `(x, y)` position is inside the rectangle.
+    #[must_use]
+    pub const fn contains(&self, position: (usize, usize)) -> bool {
+        let (x, y) = position;
+        x >= self.x && x < self.right() && y >= self.y && y < self.bottom()
+    }
+}
+
+/// Provides [`GridWriter`] for a mutable reference to a grid, e.g. to wrap a grid in an adapter
+/// such as [`Viewport`][`crate::adapter::Viewport`] without giving up ownership.
+///
+/// # Examples
+///
+/// ```
+/// # use grux::GridWriter;
+/// # use grux::adapter::Wrapping;
+/// let mut array = [['.'; 2]; 1];
+/// Wrapping::new(&mut array, 2, 1).set((3, 0), '#');
+///
+/// assert_eq!(array, [['.', '#']]);
+/// ```
+impl<G: GridWriter + ?Sized> GridWriter for &mut G {
+    type Element = G::Element;
+
+    fn set(&mut self, position: (usize, usize), element: Self::Element) {
+        (**self).set(position, element);
+    }
+}
+
+/// Provides [`GridReader`] for a reference to a grid.
+impl<G: GridReader + ?Sized> GridReader for &G {
+    type Element = G::Element;
+
+    fn get(&self, position: (usize, usize)) -> Option<&Self::Element> {
+        (**self).get(position)
+    }
+}
+
+/// Provides [`GridReader`] for a mutable reference to a grid.
+impl<G: GridReader + ?Sized> GridReader for &mut G {
+    type Element = G::Element;
+
+    fn get(&self, position: (usize, usize)) -> Option<&Self::Element> {
+        (**self).get(position)
+    }
 }
 
 /// Provides [`GridWriter`] for a fixed-size nested array of elements.
@@ -223,19 +662,42 @@ where
     }
 }
 
+/// Provides [`GridReader`] for a fixed-size nested array of elements.
+impl<const W: usize, const H: usize, T> GridReader for [[T; W]; H] {
+    type Element = T;
+
+    fn get(&self, position: (usize, usize)) -> Option<&Self::Element> {
+        let (x, y) = position;
+        self.as_slice().get(y)?.as_slice().get(x)
+    }
+}
+
 /// Provides [`DisplayGrid`] for a fixed-size nested array of elements.
+#[cfg(feature = "std")]
 impl<const W: usize, const H: usize, T> DisplayGrid for [[T; W]; H]
 where
     T: Display,
 {
     fn write_to(&self, stream: &mut impl std::io::Write) -> std::io::Result<()> {
-        for row in self {
-            for element in row {
-                write!(stream, "{}", element)?;
-            }
-            writeln!(stream)?;
-        }
-        Ok(())
+        self.write_with(stream, &display::DisplayOptions::default())
+    }
+
+    fn write_with(
+        &self,
+        stream: &mut impl std::io::Write,
+        options: &display::DisplayOptions,
+    ) -> std::io::Result<()> {
+        display::write_rows(stream, self, W, options)
+    }
+}
+
+/// Provides [`FormatGrid`] for a fixed-size nested array of elements.
+impl<const W: usize, const H: usize, T> FormatGrid for [[T; W]; H]
+where
+    T: Display,
+{
+    fn write_fmt_to(&self, out: &mut impl core::fmt::Write) -> core::fmt::Result {
+        display::format_plain(out, self)
     }
 }
 
@@ -267,6 +729,7 @@ where
 ///
 /// assert_eq!(vec, vec![vec![], vec![0, 1]]);
 /// ```
+#[cfg(feature = "alloc")]
 impl<T> GridWriter for Vec<Vec<T>>
 where
     T: Display + Default + Clone,
@@ -293,19 +756,47 @@ where
     }
 }
 
+/// Provides [`GridReader`] for a growable nested vector of elements.
+///
+/// Positions past the end of a (possibly ragged) row are out of bounds.
+#[cfg(feature = "alloc")]
+impl<T> GridReader for Vec<Vec<T>> {
+    type Element = T;
+
+    fn get(&self, position: (usize, usize)) -> Option<&Self::Element> {
+        let (x, y) = position;
+        self.as_slice().get(y)?.get(x)
+    }
+}
+
 /// Provides [`DisplayGrid`] for a growable nested vector of elements.
+#[cfg(feature = "std")]
 impl<T> DisplayGrid for Vec<Vec<T>>
 where
     T: Display + Default + Clone,
 {
     fn write_to(&self, stream: &mut impl std::io::Write) -> std::io::Result<()> {
-        for row in self {
-            for element in row {
-                write!(stream, "{}", element)?;
-            }
-            writeln!(stream)?;
-        }
-        Ok(())
+        self.write_with(stream, &display::DisplayOptions::default())
+    }
+
+    fn write_with(
+        &self,
+        stream: &mut impl std::io::Write,
+        options: &display::DisplayOptions,
+    ) -> std::io::Result<()> {
+        let width = self.iter().map(Vec::len).max().unwrap_or(0);
+        display::write_rows(stream, self, width, options)
+    }
+}
+
+/// Provides [`FormatGrid`] for a growable nested vector of elements.
+#[cfg(feature = "alloc")]
+impl<T> FormatGrid for Vec<Vec<T>>
+where
+    T: Display + Default + Clone,
+{
+    fn write_fmt_to(&self, out: &mut impl core::fmt::Write) -> core::fmt::Result {
+        display::format_plain(out, self)
     }
 }
 
@@ -341,6 +832,7 @@ where
 ///
 /// assert_eq!(string, "\n X");
 /// ```
+#[cfg(feature = "alloc")]
 impl GridWriter for String {
     type Element = char;
 
@@ -380,13 +872,31 @@ impl GridWriter for String {
 
 /// Provides [`DisplayGrid`] for a growable string of characters.
 ///
-/// > ⓘ **NOTE**: This implementation is provided for consistency, but it's already a string, so...
+/// Each line is written as a row, followed by a newline, the same as other grids; for example,
+/// `"\n X"` is written as `"\n X\n"`.
+#[cfg(feature = "std")]
 impl DisplayGrid for String {
-    fn to_string(&self) -> Result<String, FromUtf8Error> {
-        Ok(self.clone())
+    fn write_to(&self, stream: &mut impl std::io::Write) -> std::io::Result<()> {
+        self.write_with(stream, &display::DisplayOptions::default())
     }
 
-    fn write_to(&self, stream: &mut impl std::io::Write) -> std::io::Result<()> {
-        write!(stream, "{}", self)
+    fn write_with(
+        &self,
+        stream: &mut impl std::io::Write,
+        options: &display::DisplayOptions,
+    ) -> std::io::Result<()> {
+        let width = self.lines().map(|line| line.chars().count()).max();
+        let rows = self.lines().map(str::chars);
+        display::write_rows(stream, rows, width.unwrap_or(0), options)
+    }
+}
+
+/// Provides [`FormatGrid`] for a growable string of characters.
+///
+/// Each line is written as a row, followed by a newline, the same as [`DisplayGrid`].
+#[cfg(feature = "alloc")]
+impl FormatGrid for String {
+    fn write_fmt_to(&self, out: &mut impl core::fmt::Write) -> core::fmt::Result {
+        display::format_plain(out, self.lines().map(str::chars))
     }
 }
@@ -0,0 +1,306 @@
+//! Rasterize grids into RGB images, e.g. for screenshots in headless CI without a terminal.
+//!
+//! [`grux::raster`][`crate::raster`] provides:
+//! - Options for [`DisplayGrid::rasterize`][`crate::DisplayGrid::rasterize`]: [`RasterOptions`].
+//! - An RGB pixel buffer, which can be written as PPM (or PNG): [`RgbImage`].
+//!
+//! Text is drawn with a built-in bitmap font that covers printable ASCII; box-drawing characters
+//! (e.g. `╔`) and block elements (e.g. `▀`) are drawn as shapes, so they connect across cells.
+//! Other characters are drawn as a hollow box.
+//!
+//! Writing PNG files requires the `png` feature.
+
+use std::io;
+
+use crate::{
+    box_drawing, font,
+    style::{self, Color},
+};
+
+/// The width of a cell, in pixels, before scaling.
+pub const CELL_WIDTH: usize = 6;
+
+/// The height of a cell, in pixels, before scaling.
+pub const CELL_HEIGHT: usize = 10;
+
+/// Options for rasterizing a grid.
+///
+/// # Examples
+///
+/// ```
+/// # use grux::raster::RasterOptions;
+/// # use grux::style::Color;
+/// let options = RasterOptions {
+///     background: Color::Blue,
+///     scale: 2,
+///     ..RasterOptions::default()
+/// };
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RasterOptions {
+    /// The color of cells without a foreground color; defaults to white.
+    pub foreground: Color,
+
+    /// The color of cells without a background color; defaults to black.
+    pub background: Color,
+
+    /// The number of pixels each pixel of a cell is scaled to; defaults to `1`.
+    pub scale: usize,
+}
+
+impl Default for RasterOptions {
+    fn default() -> Self {
+        Self {
+            foreground: Color::White,
+            background: Color::Black,
+            scale: 1,
+        }
+    }
+}
+
+/// An image, stored as rows of 8-bit `(red, green, blue)` pixels.
+///
+/// # Examples
+///
+/// ```
+/// # use grux::raster::RgbImage;
+/// # use grux::style::Color;
+/// let mut image = RgbImage::new(2, 1, Color::Black);
+/// image.set_pixel((1, 0), (255, 0, 0));
+///
+/// let mut ppm = Vec::new();
+/// image.write_ppm(&mut ppm).unwrap();
+///
+/// assert_eq!(ppm, b"P6\n2 1\n255\n\x00\x00\x00\xff\x00\x00");
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RgbImage {
+    width: usize,
+    height: usize,
+    pixels: Vec<u8>,
+}
+
+impl RgbImage {
+    /// Creates an image of the given size, filled with the given color.
+    #[must_use]
+    pub fn new(width: usize, height: usize, color: Color) -> Self {
+        let (r, g, b) = color.to_rgb();
+        Self {
+            width,
+            height,
+            pixels: [r, g, b].repeat(width * height),
+        }
+    }
+
+    /// The width of the image, in pixels.
+    #[must_use]
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// The height of the image, in pixels.
+    #[must_use]
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Returns the pixel at the given `(x, y)` position, or `None` if it is out of bounds.
+    #[must_use]
+    pub fn get_pixel(&self, position: (usize, usize)) -> Option<(u8, u8, u8)> {
+        let (x, y) = position;
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+        let i = (y * self.width + x) * 3;
+        Some((self.pixels[i], self.pixels[i + 1], self.pixels[i + 2]))
+    }
+
+    /// Sets the pixel at the given `(x, y)` position.
+    ///
+    /// # Panics
+    ///
+    /// If the position is out of bounds.
+    pub fn set_pixel(&mut self, position: (usize, usize), rgb: (u8, u8, u8)) {
+        let (x, y) = position;
+        assert!(x < self.width && y < self.height, "Pixel out of bounds");
+        let i = (y * self.width + x) * 3;
+        self.pixels[i..i + 3].copy_from_slice(&[rgb.0, rgb.1, rgb.2]);
+    }
+
+    /// Returns the pixels as bytes, i.e. rows of `red, green, blue` triples.
+    #[must_use]
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.pixels
+    }
+
+    /// Writes the image as a binary [PPM](https://netpbm.sourceforge.net/doc/ppm.html) file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the output stream returns an error.
+    pub fn write_ppm(&self, stream: &mut impl io::Write) -> io::Result<()> {
+        write!(stream, "P6\n{} {}\n255\n", self.width, self.height)?;
+        stream.write_all(&self.pixels)
+    }
+
+    /// Writes the image as a PNG file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the output stream returns an error, or the image is too large for PNG.
+    #[cfg(feature = "png")]
+    pub fn write_png(&self, stream: &mut impl io::Write) -> io::Result<()> {
+        let size = |n: usize| u32::try_from(n).map_err(io::Error::other);
+        let mut encoder = png::Encoder::new(stream, size(self.width)?, size(self.height)?);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+        encoder
+            .write_header()
+            .and_then(|mut writer| writer.write_image_data(&self.pixels))
+            .map_err(io::Error::other)
+    }
+
+    /// Fills the pixels whose centers are inside the given rectangle, clipped to the image.
+    fn fill(&mut self, rect: Area, rgb: (u8, u8, u8)) {
+        let (x, y, width, height) = rect;
+        let range = |start: f32, length: f32, max: usize| {
+            let from = (start - 0.5).ceil().max(0.0) as usize;
+            let to = ((start + length - 0.5).ceil().max(0.0) as usize).min(max);
+            from..to
+        };
+        for py in range(y, height, self.height) {
+            for px in range(x, width, self.width) {
+                self.set_pixel((px, py), rgb);
+            }
+        }
+    }
+}
+
+/// Rasterizes text, which may contain ANSI escape codes, into an image.
+pub(crate) fn rasterize(text: &str, options: &RasterOptions) -> RgbImage {
+    let mut lines = style::parse_ansi(text);
+    if lines.last().is_some_and(Vec::is_empty) {
+        lines.pop();
+    }
+    let columns = lines
+        .iter()
+        .map(|line| line.iter().map(|(_, text)| text.chars().count()).sum())
+        .max()
+        .unwrap_or(0);
+
+    let scale = options.scale.max(1);
+    let (cell_width, cell_height) = (CELL_WIDTH * scale, CELL_HEIGHT * scale);
+    let mut image = RgbImage::new(
+        columns * cell_width,
+        lines.len() * cell_height,
+        options.background,
+    );
+
+    for (row, line) in lines.iter().enumerate() {
+        let cells = line
+            .iter()
+            .flat_map(|(style, text)| text.chars().map(move |c| (style, c)));
+        for (column, (style, c)) in cells.enumerate() {
+            let (foreground, background) = style.colors((options.foreground, options.background));
+            let background = background.unwrap_or(options.background).to_rgb();
+            let mut foreground = foreground.unwrap_or(options.foreground).to_rgb();
+            if style.dim {
+                foreground = blend(foreground, background, 0.5);
+            }
+
+            let (x, y) = ((column * cell_width) as f32, (row * cell_height) as f32);
+            let (w, h) = (cell_width as f32, cell_height as f32);
+            let s = scale as f32;
+            image.fill((x, y, w, h), background);
+
+            if let Some(arms) = box_drawing::decode(c) {
+                for (dx, dy, rw, rh) in box_drawing::segments(arms, w, h) {
+                    image.fill((x + dx, y + dy, rw, rh), foreground);
+                }
+            } else if let Some((rects, coverage)) = block(c) {
+                let color = blend(foreground, background, coverage);
+                for (dx, dy, rw, rh) in rects {
+                    image.fill((x + dx * w, y + dy * h, rw * w, rh * h), color);
+                }
+            } else if let Some(glyph) = font::glyph(c) {
+                for (gy, bits) in glyph.iter().enumerate() {
+                    for gx in 0..font::GLYPH_WIDTH {
+                        if bits & (1 << (font::GLYPH_WIDTH - 1 - gx)) == 0 {
+                            continue;
+                        }
+                        // Glyphs are offset by one row, leaving space above and below.
+                        let (px, py) = (x + gx as f32 * s, y + (gy + 1) as f32 * s);
+                        image.fill((px, py, s, s), foreground);
+                        if style.bold {
+                            image.fill((px + s, py, s, s), foreground);
+                        }
+                    }
+                }
+            } else if !c.is_whitespace() {
+                // A hollow box, for characters the font doesn't cover.
+                let (bx, by, bw, bh) = (x + s, y + s, w - 3.0 * s, h - 3.0 * s);
+                image.fill((bx, by, bw, s), foreground);
+                image.fill((bx, by + bh - s, bw, s), foreground);
+                image.fill((bx, by, s, bh), foreground);
+                image.fill((bx + bw - s, by, s, bh), foreground);
+            }
+
+            if style.underline {
+                image.fill((x, y + h - s, w, s), foreground);
+            }
+            if style.strikethrough {
+                image.fill((x, y + 4.0 * s, w, s), foreground);
+            }
+        }
+    }
+
+    image
+}
+
+/// A rectangle, as `(x, y, width, height)`.
+type Area = (f32, f32, f32, f32);
+
+/// Returns the rectangles (as fractions of a cell) and coverage of a block element, e.g. `▀`.
+///
+/// Shades (e.g. `▒`) are a single rectangle with partial coverage, i.e. a blended color.
+fn block(c: char) -> Option<(Vec<Area>, f32)> {
+    const UPPER_LEFT: Area = (0.0, 0.0, 0.5, 0.5);
+    const UPPER_RIGHT: Area = (0.5, 0.0, 0.5, 0.5);
+    const LOWER_LEFT: Area = (0.0, 0.5, 0.5, 0.5);
+    const LOWER_RIGHT: Area = (0.5, 0.5, 0.5, 0.5);
+    const FULL: Area = (0.0, 0.0, 1.0, 1.0);
+
+    let eighths = |n: u32| n as f32 / 8.0;
+    let rects = match c {
+        '▀' => vec![(0.0, 0.0, 1.0, 0.5)],
+        '▁'..='█' => {
+            let n = eighths(c as u32 - 0x2580);
+            vec![(0.0, 1.0 - n, 1.0, n)]
+        }
+        '▉'..='▏' => vec![(0.0, 0.0, eighths(0x2590 - c as u32), 1.0)],
+        '▐' => vec![(0.5, 0.0, 0.5, 1.0)],
+        '░' => return Some((vec![FULL], 0.25)),
+        '▒' => return Some((vec![FULL], 0.5)),
+        '▓' => return Some((vec![FULL], 0.75)),
+        '▔' => vec![(0.0, 0.0, 1.0, eighths(1))],
+        '▕' => vec![(eighths(7), 0.0, eighths(1), 1.0)],
+        '▖' => vec![LOWER_LEFT],
+        '▗' => vec![LOWER_RIGHT],
+        '▘' => vec![UPPER_LEFT],
+        '▙' => vec![UPPER_LEFT, LOWER_LEFT, LOWER_RIGHT],
+        '▚' => vec![UPPER_LEFT, LOWER_RIGHT],
+        '▛' => vec![UPPER_LEFT, UPPER_RIGHT, LOWER_LEFT],
+        '▜' => vec![UPPER_LEFT, UPPER_RIGHT, LOWER_RIGHT],
+        '▝' => vec![UPPER_RIGHT],
+        '▞' => vec![UPPER_RIGHT, LOWER_LEFT],
+        '▟' => vec![UPPER_RIGHT, LOWER_LEFT, LOWER_RIGHT],
+        _ => return None,
+    };
+    Some((rects, 1.0))
+}
+
+/// Mixes two colors, where `amount` is the fraction of the first color.
+fn blend(a: (u8, u8, u8), b: (u8, u8, u8), amount: f32) -> (u8, u8, u8) {
+    let mix = |a: u8, b: u8| (f32::from(a) * amount + f32::from(b) * (1.0 - amount)).round() as u8;
+    (mix(a.0, b.0), mix(a.1, b.1), mix(a.2, b.2))
+}
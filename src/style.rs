@@ -0,0 +1,499 @@
+//! Colors and text attributes for grid cells, written as ANSI escape codes.
+//!
+//! [`grux::style`][`crate::style`] provides:
+//! - A terminal color: [`Color`].
+//! - A set of colors and text attributes: [`Style`].
+//! - A cell that displays an element with a style: [`Styled`].
+//!
+//! Styled cells are written as [ANSI escape codes][], so they can be used with any grid that
+//! supports [`Display`] elements (i.e. not `String`), and are understood by exporters such as
+//! [`DisplayGrid::write_html`][`crate::DisplayGrid::write_html`].
+//!
+//! [ANSI escape codes]: https://en.wikipedia.org/wiki/ANSI_escape_code#SGR
+//!
+//! # Examples
+//!
+//! ```
+//! use grux::GridWriter;
+//! use grux::style::{Color, Style, Styled};
+//!
+//! let mut grid = [[Styled::from(' '); 2]; 1];
+//! grid.set((0, 0), Styled::new('!', Style::new().fg(Color::Red).bold()));
+//!
+//! assert_eq!(grid[0][0].to_string(), "\x1b[1;31m!\x1b[0m");
+//! assert_eq!(grid[0][1].to_string(), " ");
+//! ```
+
+use core::fmt::{self, Display};
+
+/// A terminal color.
+///
+/// The first 16 colors are the standard ANSI colors, whose exact appearance depends on the
+/// terminal; when converted to RGB, the default `xterm` palette is used.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Color {
+    Black,
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+    White,
+    BrightBlack,
+    BrightRed,
+    BrightGreen,
+    BrightYellow,
+    BrightBlue,
+    BrightMagenta,
+    BrightCyan,
+    BrightWhite,
+
+    /// One of the 256 colors of the extended `xterm` palette.
+    Indexed(u8),
+
+    /// A 24-bit "true color".
+    Rgb(u8, u8, u8),
+}
+
+/// The standard 16 colors, as used by `xterm`.
+const ANSI_PALETTE: [(u8, u8, u8); 16] = [
+    (0, 0, 0),
+    (205, 0, 0),
+    (0, 205, 0),
+    (205, 205, 0),
+    (0, 0, 238),
+    (205, 0, 205),
+    (0, 205, 205),
+    (229, 229, 229),
+    (127, 127, 127),
+    (255, 0, 0),
+    (0, 255, 0),
+    (255, 255, 0),
+    (92, 92, 255),
+    (255, 0, 255),
+    (0, 255, 255),
+    (255, 255, 255),
+];
+
+impl Color {
+    /// Returns the index of a standard ANSI color (`0..16`), or `None` for other colors.
+    #[must_use]
+    pub const fn ansi_index(&self) -> Option<u8> {
+        Some(match self {
+            Color::Black => 0,
+            Color::Red => 1,
+            Color::Green => 2,
+            Color::Yellow => 3,
+            Color::Blue => 4,
+            Color::Magenta => 5,
+            Color::Cyan => 6,
+            Color::White => 7,
+            Color::BrightBlack => 8,
+            Color::BrightRed => 9,
+            Color::BrightGreen => 10,
+            Color::BrightYellow => 11,
+            Color::BrightBlue => 12,
+            Color::BrightMagenta => 13,
+            Color::BrightCyan => 14,
+            Color::BrightWhite => 15,
+            Color::Indexed(_) | Color::Rgb(..) => return None,
+        })
+    }
+
+    /// Returns the standard ANSI color with the given index (`0..16`), or an indexed color.
+    #[must_use]
+    pub const fn from_index(index: u8) -> Self {
+        match index {
+            0 => Color::Black,
+            1 => Color::Red,
+            2 => Color::Green,
+            3 => Color::Yellow,
+            4 => Color::Blue,
+            5 => Color::Magenta,
+            6 => Color::Cyan,
+            7 => Color::White,
+            8 => Color::BrightBlack,
+            9 => Color::BrightRed,
+            10 => Color::BrightGreen,
+            11 => Color::BrightYellow,
+            12 => Color::BrightBlue,
+            13 => Color::BrightMagenta,
+            14 => Color::BrightCyan,
+            15 => Color::BrightWhite,
+            _ => Color::Indexed(index),
+        }
+    }
+
+    /// Returns the `(red, green, blue)` components of the color, using the `xterm` palette.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use grux::style::Color;
+    /// assert_eq!(Color::Red.to_rgb(), (205, 0, 0));
+    /// assert_eq!(Color::Indexed(196).to_rgb(), (255, 0, 0));
+    /// assert_eq!(Color::Rgb(1, 2, 3).to_rgb(), (1, 2, 3));
+    /// ```
+    #[must_use]
+    pub const fn to_rgb(&self) -> (u8, u8, u8) {
+        const LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+        match *self {
+            Color::Rgb(r, g, b) => (r, g, b),
+            Color::Indexed(index @ 0..=15) => ANSI_PALETTE[index as usize],
+            Color::Indexed(index @ 16..=231) => {
+                let index = (index - 16) as usize;
+                (LEVELS[index / 36], LEVELS[index / 6 % 6], LEVELS[index % 6])
+            }
+            Color::Indexed(index) => {
+                let gray = 8 + (index - 232) * 10;
+                (gray, gray, gray)
+            }
+            _ => match self.ansi_index() {
+                Some(index) => ANSI_PALETTE[index as usize],
+                None => unreachable!(),
+            },
+        }
+    }
+
+    /// Returns the color as a hex color, as used by CSS and SVG, e.g. `#cd0000`.
+    #[cfg(feature = "std")]
+    pub(crate) fn to_hex(self) -> String {
+        let (r, g, b) = self.to_rgb();
+        format!("#{r:02x}{g:02x}{b:02x}")
+    }
+
+    /// Writes the SGR parameters for this color, where `base` is `30` (foreground) or `40`.
+    fn write_sgr(&self, f: &mut impl fmt::Write, base: u8) -> fmt::Result {
+        match *self {
+            Color::Indexed(index) => write!(f, "{};5;{}", base + 8, index),
+            Color::Rgb(r, g, b) => write!(f, "{};2;{};{};{}", base + 8, r, g, b),
+            _ => match self.ansi_index() {
+                Some(index @ 0..=7) => write!(f, "{}", base + index),
+                Some(index) => write!(f, "{}", base + 60 + index - 8),
+                None => unreachable!(),
+            },
+        }
+    }
+}
+
+/// A set of colors and text attributes.
+///
+/// The default style has no colors and no attributes, i.e. uses the defaults of the terminal.
+///
+/// # Examples
+///
+/// ```
+/// # use grux::style::{Color, Style};
+/// let style = Style::new().fg(Color::Yellow).bg(Color::Blue).underline();
+///
+/// assert_eq!(style.foreground, Some(Color::Yellow));
+/// assert!(style.underline);
+/// assert!(!style.bold);
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Style {
+    /// The foreground (text) color, or `None` for the default.
+    pub foreground: Option<Color>,
+
+    /// The background color, or `None` for the default.
+    pub background: Option<Color>,
+
+    /// Bold (or increased intensity).
+    pub bold: bool,
+
+    /// Dim (or decreased intensity).
+    pub dim: bool,
+
+    /// Italic.
+    pub italic: bool,
+
+    /// Underlined.
+    pub underline: bool,
+
+    /// Foreground and background colors swapped.
+    pub reverse: bool,
+
+    /// Crossed out.
+    pub strikethrough: bool,
+}
+
+impl Style {
+    /// Creates the default style, with no colors and no attributes.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            foreground: None,
+            background: None,
+            bold: false,
+            dim: false,
+            italic: false,
+            underline: false,
+            reverse: false,
+            strikethrough: false,
+        }
+    }
+
+    /// Sets the foreground color.
+    #[must_use]
+    pub const fn fg(mut self, color: Color) -> Self {
+        self.foreground = Some(color);
+        self
+    }
+
+    /// Sets the background color.
+    #[must_use]
+    pub const fn bg(mut self, color: Color) -> Self {
+        self.background = Some(color);
+        self
+    }
+
+    /// Sets the bold attribute.
+    #[must_use]
+    pub const fn bold(mut self) -> Self {
+        self.bold = true;
+        self
+    }
+
+    /// Sets the dim attribute.
+    #[must_use]
+    pub const fn dim(mut self) -> Self {
+        self.dim = true;
+        self
+    }
+
+    /// Sets the italic attribute.
+    #[must_use]
+    pub const fn italic(mut self) -> Self {
+        self.italic = true;
+        self
+    }
+
+    /// Sets the underline attribute.
+    #[must_use]
+    pub const fn underline(mut self) -> Self {
+        self.underline = true;
+        self
+    }
+
+    /// Sets the reverse attribute.
+    #[must_use]
+    pub const fn reverse(mut self) -> Self {
+        self.reverse = true;
+        self
+    }
+
+    /// Sets the strikethrough attribute.
+    #[must_use]
+    pub const fn strikethrough(mut self) -> Self {
+        self.strikethrough = true;
+        self
+    }
+
+    /// Returns `true` if this is the default style.
+    #[must_use]
+    pub fn is_default(&self) -> bool {
+        *self == Self::new()
+    }
+
+    /// Returns the `(foreground, background)` colors to draw with, after applying `reverse`.
+    ///
+    /// When reversed, a missing color is replaced by the given default.
+    #[must_use]
+    pub fn colors(&self, default: (Color, Color)) -> (Option<Color>, Option<Color>) {
+        if self.reverse {
+            (
+                Some(self.background.unwrap_or(default.1)),
+                Some(self.foreground.unwrap_or(default.0)),
+            )
+        } else {
+            (self.foreground, self.background)
+        }
+    }
+
+    /// Writes the ANSI escape code that switches from the default style to this style.
+    ///
+    /// Nothing is written for the default style.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the formatter returns an error.
+    pub fn write_escape(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        if self.is_default() {
+            return Ok(());
+        }
+        let attributes = [
+            (self.bold, 1),
+            (self.dim, 2),
+            (self.italic, 3),
+            (self.underline, 4),
+            (self.reverse, 7),
+            (self.strikethrough, 9),
+        ];
+        let mut separator = "";
+        f.write_str("\x1b[")?;
+        for (_, code) in attributes.iter().filter(|(enabled, _)| *enabled) {
+            write!(f, "{separator}{code}")?;
+            separator = ";";
+        }
+        if let Some(color) = self.foreground {
+            f.write_str(separator)?;
+            color.write_sgr(f, 30)?;
+            separator = ";";
+        }
+        if let Some(color) = self.background {
+            f.write_str(separator)?;
+            color.write_sgr(f, 40)?;
+        }
+        f.write_str("m")
+    }
+
+    /// Applies the parameters of an SGR (`ESC [ ... m`) escape code to this style.
+    #[cfg(feature = "std")]
+    pub(crate) fn apply_sgr(&mut self, parameters: &str) {
+        let mut codes = parameters
+            .split([';', ':'])
+            .map(|code| code.parse::<u8>().unwrap_or(0));
+        while let Some(code) = codes.next() {
+            match code {
+                0 => *self = Self::new(),
+                1 => self.bold = true,
+                2 => self.dim = true,
+                3 => self.italic = true,
+                4 => self.underline = true,
+                7 => self.reverse = true,
+                9 => self.strikethrough = true,
+                22 => (self.bold, self.dim) = (false, false),
+                23 => self.italic = false,
+                24 => self.underline = false,
+                27 => self.reverse = false,
+                29 => self.strikethrough = false,
+                30..=37 => self.foreground = Some(Color::from_index(code - 30)),
+                38 => self.foreground = Self::parse_extended_color(&mut codes),
+                39 => self.foreground = None,
+                40..=47 => self.background = Some(Color::from_index(code - 40)),
+                48 => self.background = Self::parse_extended_color(&mut codes),
+                49 => self.background = None,
+                90..=97 => self.foreground = Some(Color::from_index(code - 90 + 8)),
+                100..=107 => self.background = Some(Color::from_index(code - 100 + 8)),
+                _ => {}
+            }
+        }
+    }
+
+    #[cfg(feature = "std")]
+    fn parse_extended_color(codes: &mut impl Iterator<Item = u8>) -> Option<Color> {
+        match codes.next()? {
+            5 => Some(Color::from_index(codes.next()?)),
+            2 => Some(Color::Rgb(codes.next()?, codes.next()?, codes.next()?)),
+            _ => None,
+        }
+    }
+}
+
+/// A grid cell that displays an element with a [`Style`].
+///
+/// When displayed, the element is surrounded by the ANSI escape codes for the style, unless it is
+/// the default style, in which case only the element is displayed.
+///
+/// # Examples
+///
+/// ```
+/// # #[cfg(feature = "std")]
+/// # {
+/// # use grux::DisplayGrid;
+/// # use grux::style::{Color, Style, Styled};
+/// let red = Style::new().fg(Color::Red);
+/// let grid = [[Styled::new('A', red), Styled::from('B')]];
+///
+/// assert_eq!(grid.to_string().unwrap(), "\x1b[31mA\x1b[0mB\n");
+/// # }
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Styled<T = char> {
+    /// The element, e.g. a `char`.
+    pub value: T,
+
+    /// The style the element is displayed with.
+    pub style: Style,
+}
+
+impl<T> Styled<T> {
+    /// Creates a cell that displays the given element with the given style.
+    #[must_use]
+    pub const fn new(value: T, style: Style) -> Self {
+        Self { value, style }
+    }
+}
+
+/// Creates a cell with the default style.
+impl<T> From<T> for Styled<T> {
+    fn from(value: T) -> Self {
+        Self::new(value, Style::new())
+    }
+}
+
+impl<T: Display> Display for Styled<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.style.is_default() {
+            return self.value.fmt(f);
+        }
+        self.style.write_escape(f)?;
+        self.value.fmt(f)?;
+        f.write_str("\x1b[0m")
+    }
+}
+
+/// Splits text that contains ANSI escape codes into lines of styled runs.
+///
+/// Adjacent text with the same style is merged into a single run, and escape codes other than SGR
+/// (i.e. colors and attributes) are ignored. The style carries over from one line to the next.
+#[cfg(feature = "std")]
+pub(crate) fn parse_ansi(text: &str) -> Vec<Vec<(Style, String)>> {
+    let mut lines: Vec<Vec<(Style, String)>> = vec![Vec::new()];
+    let mut style = Style::new();
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\x1b' => match chars.next() {
+                // Control Sequence Introducer, terminated by a byte in the range `@` to `~`.
+                Some('[') => {
+                    let mut parameters = String::new();
+                    for c in chars.by_ref() {
+                        if ('@'..='~').contains(&c) {
+                            if c == 'm' {
+                                style.apply_sgr(&parameters);
+                            }
+                            break;
+                        }
+                        parameters.push(c);
+                    }
+                }
+                // Operating System Command, terminated by BEL or `ESC \`.
+                Some(']') => {
+                    while let Some(c) = chars.next() {
+                        if c == '\x07' || (c == '\x1b' && chars.next_if_eq(&'\\').is_some()) {
+                            break;
+                        }
+                    }
+                }
+                _ => {}
+            },
+            '\n' => lines.push(Vec::new()),
+            '\r' => {}
+            c => {
+                let runs = lines.last_mut().unwrap();
+                match runs.last_mut() {
+                    Some((last, text)) if *last == style => text.push(c),
+                    _ => runs.push((style, c.to_string())),
+                }
+            }
+        }
+    }
+
+    lines
+}
@@ -0,0 +1,199 @@
+//! Export grids as SVG, for embedding them as vector images (e.g. in a README).
+//!
+//! [`grux::svg`][`crate::svg`] provides:
+//! - Options for [`DisplayGrid::write_svg`][`crate::DisplayGrid::write_svg`]: [`SvgOptions`].
+//!
+//! Each cell is laid out at a fixed size, and text is written in a monospace font, preserving the
+//! colors and attributes of [styled cells][`crate::style::Styled`]. Optionally, box-drawing
+//! characters (e.g. `╔`) are drawn as shapes instead, so borders are crisp and connected.
+
+use std::io;
+
+use crate::{
+    box_drawing,
+    html::escape,
+    style::{self, Color, Style},
+};
+
+/// Options for writing a grid as SVG.
+///
+/// # Examples
+///
+/// ```
+/// # use grux::style::Color;
+/// # use grux::svg::SvgOptions;
+/// let options = SvgOptions {
+///     background: Some(Color::Black),
+///     foreground: Color::White,
+///     box_drawing: true,
+///     ..SvgOptions::default()
+/// };
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct SvgOptions {
+    /// The width of each cell, in pixels; defaults to `10`.
+    pub cell_width: f32,
+
+    /// The height of each cell, in pixels; defaults to `20`.
+    pub cell_height: f32,
+
+    /// The font family of the text; defaults to `monospace`.
+    pub font_family: String,
+
+    /// The font size of the text, in pixels; defaults to `16`.
+    pub font_size: f32,
+
+    /// The color of cells without a foreground color; defaults to black.
+    pub foreground: Color,
+
+    /// The color behind the grid, or `None` (the default) for transparent.
+    pub background: Option<Color>,
+
+    /// Whether to draw box-drawing characters as shapes instead of text; defaults to `false`.
+    pub box_drawing: bool,
+}
+
+impl Default for SvgOptions {
+    fn default() -> Self {
+        Self {
+            cell_width: 10.0,
+            cell_height: 20.0,
+            font_family: "monospace".to_string(),
+            font_size: 16.0,
+            foreground: Color::Black,
+            background: None,
+            box_drawing: false,
+        }
+    }
+}
+
+/// Writes text, which may contain ANSI escape codes, as SVG.
+pub(crate) fn write_svg(
+    text: &str,
+    stream: &mut impl io::Write,
+    options: &SvgOptions,
+) -> io::Result<()> {
+    let mut lines = style::parse_ansi(text);
+    if lines.last().is_some_and(Vec::is_empty) {
+        lines.pop();
+    }
+
+    let (cell_width, cell_height) = (options.cell_width, options.cell_height);
+    let columns = lines
+        .iter()
+        .map(|line| line.iter().map(|(_, text)| text.chars().count()).sum())
+        .max()
+        .unwrap_or(0);
+    let width = columns as f32 * cell_width;
+    let height = lines.len() as f32 * cell_height;
+
+    writeln!(
+        stream,
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width}\" height=\"{height}\" \
+         viewBox=\"0 0 {width} {height}\" font-family=\"{}\" font-size=\"{}\">",
+        escape(&options.font_family),
+        options.font_size,
+    )?;
+    if let Some(background) = options.background {
+        writeln!(
+            stream,
+            "<rect width=\"100%\" height=\"100%\" fill=\"{}\"/>",
+            background.to_hex()
+        )?;
+    }
+
+    let defaults = (
+        options.foreground,
+        options.background.unwrap_or(Color::BrightWhite),
+    );
+    for (row, line) in lines.iter().enumerate() {
+        let y = row as f32 * cell_height;
+        let mut column = 0;
+        for (style, text) in line {
+            let (foreground, background) = style.colors(defaults);
+            let foreground = foreground.unwrap_or(options.foreground);
+            let length = text.chars().count();
+            if let Some(background) = background {
+                writeln!(
+                    stream,
+                    "<rect x=\"{}\" y=\"{y}\" width=\"{}\" height=\"{cell_height}\" fill=\"{}\"/>",
+                    column as f32 * cell_width,
+                    length as f32 * cell_width,
+                    background.to_hex(),
+                )?;
+            }
+
+            // Split the run into text, and box-drawing characters drawn as shapes.
+            let mut start = column;
+            let mut pending = String::new();
+            for (i, c) in text.chars().enumerate() {
+                let arms = options
+                    .box_drawing
+                    .then(|| box_drawing::decode(c))
+                    .flatten();
+                let Some(arms) = arms else {
+                    pending.push(c);
+                    continue;
+                };
+                write_text(stream, &pending, (start, row), style, foreground, options)?;
+                pending.clear();
+                start = column + i + 1;
+
+                let x = (column + i) as f32 * cell_width;
+                for (dx, dy, w, h) in box_drawing::segments(arms, cell_width, cell_height) {
+                    writeln!(
+                        stream,
+                        "<rect x=\"{}\" y=\"{}\" width=\"{w}\" height=\"{h}\" fill=\"{}\"/>",
+                        x + dx,
+                        y + dy,
+                        foreground.to_hex(),
+                    )?;
+                }
+            }
+            write_text(stream, &pending, (start, row), style, foreground, options)?;
+            column += length;
+        }
+    }
+
+    writeln!(stream, "</svg>")
+}
+
+/// Writes a `<text>` element, stretched to exactly fill its cells, unless it is blank.
+fn write_text(
+    stream: &mut impl io::Write,
+    text: &str,
+    cell: (usize, usize),
+    style: &Style,
+    color: Color,
+    options: &SvgOptions,
+) -> io::Result<()> {
+    if text.trim().is_empty() {
+        return Ok(());
+    }
+    let x = cell.0 as f32 * options.cell_width;
+    let y = (cell.1 as f32 + 0.5) * options.cell_height;
+    let length = text.chars().count() as f32 * options.cell_width;
+
+    write!(
+        stream,
+        "<text x=\"{x}\" y=\"{y}\" textLength=\"{length}\" lengthAdjust=\"spacingAndGlyphs\" \
+         dominant-baseline=\"central\" xml:space=\"preserve\" fill=\"{}\"",
+        color.to_hex()
+    )?;
+    if style.bold {
+        write!(stream, " font-weight=\"bold\"")?;
+    }
+    if style.dim {
+        write!(stream, " opacity=\"0.5\"")?;
+    }
+    if style.italic {
+        write!(stream, " font-style=\"italic\"")?;
+    }
+    match (style.underline, style.strikethrough) {
+        (true, true) => write!(stream, " text-decoration=\"underline line-through\"")?,
+        (true, false) => write!(stream, " text-decoration=\"underline\"")?,
+        (false, true) => write!(stream, " text-decoration=\"line-through\"")?,
+        (false, false) => {}
+    }
+    writeln!(stream, ">{}</text>", escape(text))
+}
@@ -0,0 +1,272 @@
+//! Helpers for testing code that draws to grids, with readable failure messages.
+//!
+//! [`grux::testing`][`crate::testing`] provides:
+//! - An assertion that two grids display the same: [`assert_grid_eq!`][`crate::assert_grid_eq`].
+//! - A side-by-side description of the differences between two grids: [`grid_diff`].
+//! - File-based golden snapshots of grids: [`assert_snapshot`].
+//!
+//! Grids are compared by what they display, cell by cell, so different grid types (e.g. an array
+//! and a [`Grid`][`crate::grid::Grid`]) can be compared, and styled cells are compared by both
+//! their character and their [`Style`].
+//!
+//! # Examples
+//!
+//! ```should_panic
+//! use grux::assert_grid_eq;
+//!
+//! let grid = [['╔', '═', '╗'], ['╚', '═', '╝']];
+//!
+//! // Panics with:
+//! //
+//! // grids differ at 1 cell(s), as (x, y): (1, 1)
+//! // left │ right │ diff
+//! // ╔═╗  │ ╔═╗   │
+//! // ╚═╝  │ ╚-╝   │  ^
+//! assert_grid_eq!(grid, [['╔', '═', '╗'], ['╚', '-', '╝']]);
+//! ```
+
+use std::{env, fs, path::Path};
+
+use crate::{
+    style::{self, Style},
+    DisplayGrid,
+};
+
+/// The environment variable that, when set (to anything other than `0`), makes
+/// [`assert_snapshot`] write snapshots instead of comparing against them.
+pub const UPDATE_SNAPSHOTS: &str = "GRUX_UPDATE_SNAPSHOTS";
+
+/// The maximum number of mismatched positions listed by [`grid_diff`].
+const MAX_LISTED: usize = 20;
+
+/// Asserts that two grids display the same, cell by cell.
+///
+/// On failure, panics with both grids side by side and the positions of the differing cells; see
+/// [`grid_diff`]. Like [`assert_eq!`], a custom message can be given after the grids.
+///
+/// # Examples
+///
+/// ```
+/// # use grux::assert_grid_eq;
+/// # use grux::grid::Grid;
+/// let grid: Grid<char> = "ab\ncd".parse().unwrap();
+///
+/// assert_grid_eq!(grid, [['a', 'b'], ['c', 'd']]);
+/// assert_grid_eq!(grid, "ab\ncd\n".to_string(), "parsed from {:?}", "ab\ncd");
+/// ```
+#[macro_export]
+macro_rules! assert_grid_eq {
+    ($left:expr, $right:expr $(,)?) => {
+        if let Some(diff) = $crate::testing::grid_diff(&$left, &$right) {
+            panic!("assertion `left == right` failed: grids differ\n{}", diff);
+        }
+    };
+    ($left:expr, $right:expr, $($arg:tt)+) => {
+        if let Some(diff) = $crate::testing::grid_diff(&$left, &$right) {
+            panic!(
+                "assertion `left == right` failed: {}\n{}",
+                format_args!($($arg)+),
+                diff
+            );
+        }
+    };
+}
+
+/// Describes the differences between two grids, or returns `None` if they display the same.
+///
+/// The description lists the `(x, y)` positions of the differing cells, followed by both grids
+/// (without styles) side by side, with a `^` marking each differing cell. Cells that only differ
+/// in style are listed with a `*`.
+///
+/// # Examples
+///
+/// ```
+/// # use grux::testing::grid_diff;
+/// let left = [['a', 'b'], ['c', 'd']];
+///
+/// assert_eq!(grid_diff(&left, &[['a', 'b'], ['c', 'd']]), None);
+/// assert_eq!(
+///     grid_diff(&left, &[['a', 'b'], ['c', 'x']]).unwrap(),
+///     "grids differ at 1 cell(s), as (x, y): (1, 1)\n\
+///      left │ right │ diff\n\
+///      ab   │ ab    │\n\
+///      cd   │ cx    │  ^\n"
+/// );
+/// ```
+#[must_use]
+pub fn grid_diff(left: &impl DisplayGrid, right: &impl DisplayGrid) -> Option<String> {
+    diff("left", &cells_of(left), "right", &cells_of(right))
+}
+
+/// Asserts that a grid displays the same as the snapshot in the given file.
+///
+/// If the [`UPDATE_SNAPSHOTS`] environment variable is set, the snapshot is written instead, e.g.
+/// `GRUX_UPDATE_SNAPSHOTS=1 cargo test`. Relative paths are relative to the current directory,
+/// which is the package root when running `cargo test`.
+///
+/// # Panics
+///
+/// If the grid differs from the snapshot, or the snapshot cannot be read (or written).
+///
+/// # Examples
+///
+/// ```no_run
+/// # use grux::testing::assert_snapshot;
+/// let grid = [['╔', '═', '╗'], ['╚', '═', '╝']];
+///
+/// assert_snapshot("tests/snapshots/box.txt", &grid);
+/// ```
+#[track_caller]
+pub fn assert_snapshot(path: impl AsRef<Path>, actual: &impl DisplayGrid) {
+    let update = env::var_os(UPDATE_SNAPSHOTS).is_some_and(|value| value != "0");
+    check_snapshot(path.as_ref(), actual, update);
+}
+
+/// Compares a grid against the snapshot in the given file, or writes it if `update` is set.
+///
+/// This is [`assert_snapshot`] without reading [`UPDATE_SNAPSHOTS`], so that both paths can be
+/// tested without changing the environment of other tests.
+#[track_caller]
+pub(crate) fn check_snapshot(path: &Path, actual: &impl DisplayGrid, update: bool) {
+    let mut output = Vec::new();
+    actual.write_to(&mut output).expect("Grid must be writable");
+
+    if update {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).expect("Snapshot directory must be writable");
+        }
+        fs::write(path, output).expect("Snapshot must be writable");
+        return;
+    }
+
+    let snapshot = match fs::read_to_string(path) {
+        Ok(snapshot) => snapshot.replace("\r\n", "\n"),
+        Err(error) => panic!(
+            "snapshot {} could not be read ({}); run with {}=1 to create it",
+            path.display(),
+            error,
+            UPDATE_SNAPSHOTS
+        ),
+    };
+    let actual = style::parse_ansi(&String::from_utf8_lossy(&output));
+    if let Some(diff) = diff("snapshot", &lines_of(&snapshot), "actual", &trim(actual)) {
+        panic!(
+            "snapshot {} does not match; run with {}=1 to update it\n{}",
+            path.display(),
+            UPDATE_SNAPSHOTS,
+            diff
+        );
+    }
+}
+
+/// A row of cells, as displayed.
+type Row = Vec<(Style, char)>;
+
+/// Returns the rows of cells that a grid displays.
+fn cells_of(grid: &impl DisplayGrid) -> Vec<Row> {
+    let mut output = Vec::new();
+    grid.write_to(&mut output).expect("Grid must be writable");
+    lines_of(&String::from_utf8_lossy(&output))
+}
+
+/// Returns the rows of cells of text, which may contain ANSI escape codes.
+fn lines_of(text: &str) -> Vec<Row> {
+    trim(style::parse_ansi(text))
+}
+
+/// Flattens lines of styled runs into rows of cells, ignoring the empty line after a trailing
+/// newline.
+fn trim(mut lines: Vec<Vec<(Style, String)>>) -> Vec<Row> {
+    if lines.last().is_some_and(Vec::is_empty) {
+        lines.pop();
+    }
+    lines
+        .into_iter()
+        .map(|line| {
+            line.into_iter()
+                .flat_map(|(style, text)| text.chars().map(move |c| (style, c)).collect::<Vec<_>>())
+                .collect()
+        })
+        .collect()
+}
+
+/// Describes the differences between two sets of rows, or returns `None` if they are equal.
+fn diff(left_name: &str, left: &[Row], right_name: &str, right: &[Row]) -> Option<String> {
+    if left == right {
+        return None;
+    }
+
+    let cell =
+        |rows: &[Row], (x, y): (usize, usize)| rows.get(y).and_then(|row| row.get(x)).copied();
+    let height = left.len().max(right.len());
+    let mut mismatches = Vec::new();
+    for y in 0..height {
+        let width = [left, right].map(|rows| rows.get(y).map_or(0, Vec::len));
+        for x in 0..width[0].max(width[1]) {
+            let (a, b) = (cell(left, (x, y)), cell(right, (x, y)));
+            if a != b {
+                let style_only = a.zip(b).is_some_and(|(a, b)| a.1 == b.1);
+                mismatches.push(((x, y), style_only));
+            }
+        }
+    }
+
+    let mut out = format!("grids differ at {} cell(s), as (x, y): ", mismatches.len());
+    let listed = mismatches
+        .iter()
+        .take(MAX_LISTED)
+        .map(|((x, y), style_only)| {
+            format!("({}, {}){}", x, y, if *style_only { "*" } else { "" })
+        });
+    out.push_str(&listed.collect::<Vec<_>>().join(", "));
+    if mismatches.len() > MAX_LISTED {
+        out.push_str(&format!(", and {} more", mismatches.len() - MAX_LISTED));
+    }
+    out.push('\n');
+
+    let text = |rows: &[Row], y: usize| -> String {
+        rows.get(y)
+            .map_or_else(String::new, |row| row.iter().map(|(_, c)| c).collect())
+    };
+    let column = |name: &str, rows: &[Row]| {
+        rows.iter()
+            .map(Vec::len)
+            .max()
+            .unwrap_or(0)
+            .max(name.chars().count())
+    };
+    let (left_width, right_width) = (column(left_name, left), column(right_name, right));
+    out.push_str(&format!(
+        "{:<lw$} │ {:<rw$} │ diff\n",
+        left_name,
+        right_name,
+        lw = left_width,
+        rw = right_width
+    ));
+    for y in 0..height {
+        let markers: String = (0..mismatches
+            .iter()
+            .map(|((x, _), _)| x + 1)
+            .max()
+            .unwrap_or(0))
+            .map(|x| {
+                if mismatches.iter().any(|(position, _)| *position == (x, y)) {
+                    '^'
+                } else {
+                    ' '
+                }
+            })
+            .collect();
+        let line = format!(
+            "{:<lw$} │ {:<rw$} │ {}",
+            text(left, y),
+            text(right, y),
+            markers,
+            lw = left_width,
+            rw = right_width
+        );
+        out.push_str(line.trim_end());
+        out.push('\n');
+    }
+    Some(out)
+}
@@ -1,4 +1,19 @@
+use std::time::Duration;
+
+use super::adapter::*;
 use super::art::*;
+use super::asciicast::*;
+use super::display::*;
+use super::grid::*;
+use super::html::*;
+use super::layout::*;
+use super::raster::*;
+use super::style::*;
+use super::svg::*;
+use super::testing::*;
+use super::text::*;
+#[cfg(feature = "xp")]
+use super::xp::*;
 use super::*;
 
 #[test]
@@ -186,3 +201,1950 @@ fn sprite_border_width_too_small() {
 fn sprite_border_height_too_small() {
     let _ = BorderRect::new(3, 1, ['╔', '═', '╗', '║', '║', '╚', '═', '╝']);
 }
+
+#[test]
+fn sparse_grid_negative_positions() {
+    let mut grid = SparseGrid::new('.');
+
+    grid.insert((-2, -1), 'A');
+    grid.insert((0, 1), 'B');
+
+    assert_eq!(grid.len(), 2);
+    assert_eq!(grid.bounds(), Some(((-2, -1), (0, 1))));
+    assert_eq!(grid.get((-2, -1)), Some(&'A'));
+    assert_eq!(grid.get((-1, -1)), None);
+    assert_eq!(grid.get_or_default((-1, -1)), &'.');
+}
+
+#[test]
+fn sparse_grid_remove_shrinks_bounds() {
+    let mut grid = SparseGrid::new(' ');
+
+    grid.insert((0, 0), 'A');
+    grid.insert((5, 5), 'B');
+    assert_eq!(grid.remove((5, 5)), Some('B'));
+
+    assert_eq!(grid.bounds(), Some(((0, 0), (0, 0))));
+    assert_eq!(grid.remove((5, 5)), None);
+    assert_eq!(grid.remove((0, 0)), Some('A'));
+    assert_eq!(grid.bounds(), None);
+    assert!(grid.is_empty());
+}
+
+#[test]
+fn sparse_grid_writer_origin() {
+    let mut grid = SparseGrid::new('.');
+    grid.set_origin((-1, -1));
+
+    let rect = BorderRect::new(3, 3, ['╔', '═', '╗', '║', '║', '╚', '═', '╝']);
+    rect.draw_to((0, 0), &mut grid);
+
+    assert_eq!(grid.bounds(), Some(((-1, -1), (1, 1))));
+    assert_eq!(grid.get((0, 0)), None);
+    assert_eq!(grid.to_string().unwrap(), "╔═╗\n║.║\n╚═╝\n");
+}
+
+#[test]
+fn display_grid_sparse_empty() {
+    let grid: SparseGrid<char> = SparseGrid::default();
+
+    assert_eq!(grid.to_string().unwrap(), "");
+}
+
+#[test]
+fn chunked_grid_allocates_on_write() {
+    let mut grid = ChunkedGrid::new(4, 4, ' ');
+
+    assert_eq!(grid.get((10, 10)), &' ');
+    assert!(!grid.is_loaded((2, 2)));
+
+    grid.set((10, 10), 'X');
+
+    assert_eq!(grid.get((10, 10)), &'X');
+    assert!(grid.is_loaded((2, 2)));
+    assert_eq!(grid.loaded_chunks().count(), 1);
+}
+
+#[test]
+fn chunked_grid_unload_and_load() {
+    let mut grid = ChunkedGrid::new(2, 2, 0);
+    grid.set((3, 1), 7);
+
+    let cells = grid.unload((1, 0)).unwrap();
+    assert_eq!(cells, vec![0, 0, 0, 7]);
+    assert_eq!(grid.get((3, 1)), &0);
+
+    grid.load((5, 5), cells);
+    assert_eq!(grid.get((11, 11)), &7);
+}
+
+#[test]
+#[should_panic]
+fn chunked_grid_load_wrong_size() {
+    let mut grid = ChunkedGrid::new(2, 2, 0);
+    grid.load((0, 0), vec![1, 2, 3]);
+}
+
+#[test]
+fn chunked_grid_loader() {
+    let mut grid =
+        ChunkedGrid::new(1, 2, '.').with_loader(|(x, _)| (x % 2 == 0).then(|| vec!['A', 'B']));
+
+    grid.set((0, 0), 'X');
+    grid.set((1, 0), 'Y');
+
+    assert_eq!(grid.get((0, 1)), &'B');
+    assert_eq!(grid.get((1, 1)), &'.');
+}
+
+#[test]
+fn chunked_grid_reads_only_loaded_chunks() {
+    let mut grid =
+        ChunkedGrid::new(1, 2, '.').with_loader(|(x, _)| (x % 2 == 0).then(|| vec!['A', 'B']));
+
+    assert_eq!(grid.get((0, 1)), &'.');
+    assert_eq!(GridReader::get(&grid, (0, 1)), Some(&'.'));
+    assert!(!grid.is_loaded((0, 0)));
+
+    assert_eq!(grid.get_or_load((0, 1)), &'B');
+    assert_eq!(grid.get_or_load((1, 1)), &'.');
+    assert_eq!(grid.loaded_chunks().collect::<Vec<_>>(), vec![(0, 0)]);
+    assert_eq!(grid.get((0, 1)), &'B');
+}
+
+#[test]
+fn wrapping_grid_writer() {
+    let mut grid = Wrapping::from([[0; 3]; 2]);
+
+    grid.set((4, 5), 1);
+    grid.set((3, 0), 2);
+
+    assert_eq!(grid.size(), (3, 2));
+    assert_eq!(grid.inner(), &[[2, 0, 0], [0, 1, 0]]);
+}
+
+#[test]
+fn wrapping_fill_rect_across_corner() {
+    let mut grid = Wrapping::new(vec![vec![' '; 3]; 3], 3, 3);
+
+    let rect = FillRect::new(2, 2, '█');
+    rect.draw_to((2, 2), &mut grid);
+
+    assert_eq!(grid.to_string().unwrap(), "█ █\n   \n█ █\n");
+}
+
+#[test]
+#[should_panic]
+fn wrapping_zero_width() {
+    let _ = Wrapping::new(String::new(), 0, 1);
+}
+
+#[test]
+fn rect_contains() {
+    let rect = Rect::new(1, 1, 2, 2);
+
+    assert!(rect.contains((1, 1)));
+    assert!(rect.contains((2, 2)));
+    assert!(!rect.contains((3, 2)));
+    assert!(!rect.contains((0, 1)));
+    assert!(Rect::new(1, 1, 0, 2).is_empty());
+}
+
+#[test]
+fn tracked_coalesces_runs() {
+    let mut grid = Tracked::new([[0; 8]; 2]);
+
+    grid.set((5, 0), 1);
+    grid.set((1, 0), 1);
+    grid.set((2, 0), 1);
+    grid.set((4, 0), 1);
+    grid.set((7, 1), 1);
+    grid.set((2, 0), 2);
+
+    #[rustfmt::skip]
+    assert_eq!(grid.take_dirty(), vec![
+        Rect::new(1, 0, 2, 1),
+        Rect::new(4, 0, 2, 1),
+        Rect::new(7, 1, 1, 1),
+    ]);
+
+    grid.set((3, 0), 1);
+    assert_eq!(grid.take_dirty(), vec![Rect::new(3, 0, 1, 1)]);
+    assert!(grid.take_dirty().is_empty());
+}
+
+#[test]
+fn tracked_merges_gap() {
+    let mut grid = Tracked::new(vec![vec![0; 4]]);
+
+    grid.set((0, 0), 1);
+    grid.set((2, 0), 1);
+    grid.set((1, 0), 1);
+
+    assert_eq!(grid.take_dirty(), vec![Rect::new(0, 0, 3, 1)]);
+    assert_eq!(grid.into_inner(), vec![vec![1, 1, 1, 0]]);
+}
+
+#[test]
+fn tracked_mark_dirty() {
+    let mut grid = Tracked::new(String::new());
+
+    grid.mark_dirty(Rect::new(0, 0, 2, 2));
+
+    #[rustfmt::skip]
+    assert_eq!(grid.take_dirty(), vec![
+        Rect::new(0, 0, 2, 1),
+        Rect::new(0, 1, 2, 1),
+    ]);
+}
+
+#[test]
+fn grid_reader_fixed_array() {
+    let grid = [[0, 1], [2, 3]];
+
+    assert_eq!(GridReader::get(&grid, (1, 0)), Some(&1));
+    assert_eq!(GridReader::get(&grid, (0, 2)), None);
+}
+
+#[test]
+fn grid_reader_ragged_vec() {
+    let grid = vec![vec![], vec![0, 1]];
+
+    assert_eq!(GridReader::get(&grid, (0, 0)), None);
+    assert_eq!(GridReader::get(&grid, (1, 1)), Some(&1));
+}
+
+#[test]
+fn history_undo_redo_single_writes() {
+    let mut grid = History::new([[0; 2]; 1]);
+
+    grid.set((0, 0), 1);
+    grid.set((0, 0), 2);
+
+    assert!(grid.undo());
+    assert_eq!(grid.inner(), &[[1, 0]]);
+    assert!(grid.undo());
+    assert_eq!(grid.inner(), &[[0, 0]]);
+    assert!(!grid.undo());
+
+    assert!(grid.redo());
+    assert!(grid.redo());
+    assert!(!grid.redo());
+    assert_eq!(grid.inner(), &[[2, 0]]);
+}
+
+#[test]
+fn history_transaction() {
+    let mut grid = History::new(vec![vec![' '; 3]; 3]);
+
+    grid.begin();
+    grid.set((0, 0), 'A');
+    grid.begin();
+    grid.set((1, 1), 'B');
+    grid.commit();
+    grid.set((2, 2), 'C');
+    grid.commit();
+
+    assert!(grid.undo());
+    assert_eq!(grid.inner(), &vec![vec![' '; 3]; 3]);
+    assert!(!grid.can_undo());
+    assert!(grid.can_redo());
+}
+
+#[test]
+fn history_undo_during_transaction() {
+    let mut grid = History::new([[0; 2]; 1]);
+
+    grid.begin();
+    grid.set((0, 0), 1);
+    assert!(grid.undo());
+    assert_eq!(grid.inner(), &[[0, 0]]);
+    grid.set((1, 0), 2);
+    grid.commit();
+
+    assert_eq!(grid.inner(), &[[0, 2]]);
+    assert!(!grid.can_redo());
+    assert!(grid.undo());
+    assert_eq!(grid.inner(), &[[0, 0]]);
+    assert!(!grid.undo());
+}
+
+#[test]
+fn history_redo_during_transaction() {
+    let mut grid = History::new([[0; 2]; 1]);
+
+    grid.set((0, 0), 1);
+    grid.undo();
+    grid.begin();
+    assert!(grid.redo());
+    grid.set((1, 0), 2);
+    assert!(!grid.redo());
+    grid.commit();
+    assert_eq!(grid.inner(), &[[1, 2]]);
+
+    assert!(grid.undo());
+    assert_eq!(grid.inner(), &[[1, 0]]);
+    assert!(grid.undo());
+    assert_eq!(grid.inner(), &[[0, 0]]);
+    assert!(!grid.undo());
+}
+
+#[test]
+fn history_write_clears_redo() {
+    let mut grid = History::new([[0; 2]; 1]);
+
+    grid.set((0, 0), 1);
+    grid.undo();
+    grid.set((1, 0), 1);
+
+    assert!(!grid.can_redo());
+    assert!(!grid.redo());
+    assert_eq!(grid.inner(), &[[0, 1]]);
+}
+
+#[test]
+fn history_undo_grown_vec() {
+    let mut grid = History::new(Vec::new());
+
+    grid.draw((1, 0), &Line::horizontal(2, 5));
+    grid.undo();
+
+    assert_eq!(grid.into_inner(), vec![vec![0, 0, 0]]);
+}
+
+#[test]
+fn history_step_over_budget() {
+    let mut grid = History::new([[' '; 4]; 4]).with_budget(8);
+
+    grid.draw((0, 0), &FillRect::new(3, 3, '█'));
+
+    assert!(!grid.can_undo());
+    assert_eq!(grid.inner()[2][2], '█');
+}
+
+#[test]
+#[should_panic]
+fn history_commit_without_begin() {
+    let mut grid = History::new([[0; 1]; 1]);
+    grid.commit();
+}
+
+#[test]
+fn style_escape_codes() {
+    let style = Style::new()
+        .fg(Color::BrightBlue)
+        .bg(Color::Rgb(1, 2, 3))
+        .italic()
+        .strikethrough();
+
+    assert_eq!(
+        Styled::new('x', style).to_string(),
+        "\x1b[3;9;94;48;2;1;2;3mx\x1b[0m"
+    );
+    assert_eq!(
+        Styled::new('x', Style::new().bg(Color::Indexed(42))).to_string(),
+        "\x1b[48;5;42mx\x1b[0m"
+    );
+}
+
+#[test]
+fn style_parse_ansi_round_trip() {
+    let styles = [
+        Style::new(),
+        Style::new().fg(Color::Red).bold(),
+        Style::new().bg(Color::BrightWhite).dim().underline(),
+        Style::new()
+            .fg(Color::Indexed(200))
+            .bg(Color::Rgb(9, 8, 7))
+            .reverse(),
+    ];
+    let text: String = styles
+        .iter()
+        .map(|style| Styled::new('x', *style).to_string())
+        .collect();
+
+    let lines = parse_ansi(&text);
+    let parsed: Vec<Style> = lines[0].iter().map(|(style, _)| *style).collect();
+
+    assert_eq!(parsed, styles);
+}
+
+#[test]
+fn style_parse_ansi_merges_runs() {
+    let text = "\x1b[31mab\x1b[0m\x1b[31mc\x1b[0md\n\x1b]0;title\x07e\x1b[2Jf";
+
+    let red = Style::new().fg(Color::Red);
+    #[rustfmt::skip]
+    assert_eq!(parse_ansi(text), vec![
+        vec![(red, "abc".to_string()), (Style::new(), "d".to_string())],
+        vec![(Style::new(), "ef".to_string())],
+    ]);
+}
+
+#[test]
+fn display_grid_write_html_standalone() {
+    let grid = [['"', '\''], ['a', 'b']];
+    let options = HtmlOptions {
+        standalone: true,
+        title: Some("<grid>".to_string()),
+        class: Some("art".to_string()),
+    };
+
+    let mut output = Vec::new();
+    grid.write_html(&mut output, &options).unwrap();
+
+    assert_eq!(
+        String::from_utf8(output).unwrap(),
+        "<!DOCTYPE html>\n\
+         <html>\n\
+         <head>\n\
+         <meta charset=\"utf-8\">\n\
+         <title>&lt;grid&gt;</title>\n\
+         </head>\n\
+         <body>\n\
+         <pre class=\"art\">&quot;&#39;\nab\n</pre>\n\
+         </body>\n\
+         </html>\n"
+    );
+}
+
+#[test]
+fn display_grid_write_html_attributes() {
+    let style = Style::new()
+        .bold()
+        .dim()
+        .italic()
+        .underline()
+        .strikethrough()
+        .reverse();
+    let grid = vec![vec![Styled::new('x', style)]];
+
+    let mut output = Vec::new();
+    grid.write_html(&mut output, &HtmlOptions::default())
+        .unwrap();
+
+    assert_eq!(
+        String::from_utf8(output).unwrap(),
+        "<pre><span style=\"color:#ffffff;background-color:#000000;font-weight:bold;\
+         opacity:0.5;font-style:italic;text-decoration:underline line-through\">x</span>\n</pre>\n"
+    );
+}
+
+#[test]
+fn box_drawing_decode() {
+    use super::box_drawing::{decode, Weight::*};
+
+    assert_eq!(decode('┼'), Some([Some(Light); 4]));
+    assert_eq!(decode('╔'), Some([None, Some(Double), Some(Double), None]));
+    assert_eq!(decode('╭'), decode('┌'));
+    assert_eq!(decode('x'), None);
+}
+
+#[test]
+fn box_drawing_encode() {
+    use super::box_drawing::{decode, encode, Weight::*};
+
+    assert_eq!(encode([Some(Light); 4]), Some('┼'));
+    assert_eq!(
+        encode([None, Some(Double), Some(Light), Some(Double)]),
+        Some('╤')
+    );
+    assert_eq!(encode(decode('╭').unwrap()), Some('┌'));
+    assert_eq!(encode([Some(Double), Some(Heavy), None, None]), None);
+}
+
+#[test]
+fn box_drawing_segments_light_corner() {
+    use super::box_drawing::{decode, segments};
+
+    let rects = segments(decode('┌').unwrap(), 8.0, 16.0);
+
+    assert_eq!(rects, vec![(3.5, 7.5, 4.5, 1.0), (3.5, 7.5, 1.0, 8.5)]);
+}
+
+#[test]
+fn box_drawing_segments_double_corner() {
+    use super::box_drawing::{decode, segments};
+
+    let rects = segments(decode('╔').unwrap(), 8.0, 8.0);
+
+    // Outer strokes extend past the center, inner strokes stop short of it.
+    #[rustfmt::skip]
+    assert_eq!(rects, vec![
+        (2.5, 2.5, 5.5, 1.0),
+        (4.5, 4.5, 3.5, 1.0),
+        (2.5, 2.5, 1.0, 5.5),
+        (4.5, 4.5, 1.0, 3.5),
+    ]);
+}
+
+#[test]
+fn display_grid_write_svg_styles() {
+    let style = Style::new()
+        .fg(Color::Red)
+        .bg(Color::Blue)
+        .bold()
+        .underline();
+    let grid = [
+        [Styled::new('&', style), Styled::from(' ')],
+        [Styled::from('b'); 2],
+    ];
+    let options = SvgOptions {
+        background: Some(Color::Black),
+        ..SvgOptions::default()
+    };
+
+    let mut output = Vec::new();
+    grid.write_svg(&mut output, &options).unwrap();
+
+    assert_eq!(
+        String::from_utf8(output).unwrap(),
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"20\" height=\"40\" \
+         viewBox=\"0 0 20 40\" font-family=\"monospace\" font-size=\"16\">\n\
+         <rect width=\"100%\" height=\"100%\" fill=\"#000000\"/>\n\
+         <rect x=\"0\" y=\"0\" width=\"10\" height=\"20\" fill=\"#0000ee\"/>\n\
+         <text x=\"0\" y=\"10\" textLength=\"10\" lengthAdjust=\"spacingAndGlyphs\" \
+         dominant-baseline=\"central\" xml:space=\"preserve\" fill=\"#cd0000\" \
+         font-weight=\"bold\" text-decoration=\"underline\">&amp;</text>\n\
+         <text x=\"0\" y=\"30\" textLength=\"20\" lengthAdjust=\"spacingAndGlyphs\" \
+         dominant-baseline=\"central\" xml:space=\"preserve\" fill=\"#000000\">bb</text>\n\
+         </svg>\n"
+    );
+}
+
+#[test]
+fn display_grid_write_svg_box_drawing() {
+    let grid = String::from("a─b");
+    let options = SvgOptions {
+        cell_width: 8.0,
+        cell_height: 8.0,
+        box_drawing: true,
+        ..SvgOptions::default()
+    };
+
+    let mut output = Vec::new();
+    grid.write_svg(&mut output, &options).unwrap();
+    let svg = String::from_utf8(output).unwrap();
+
+    assert!(svg.contains("<text x=\"0\" y=\"4\" textLength=\"8\""));
+    assert!(svg.contains("<rect x=\"11.5\" y=\"3.5\" width=\"4.5\" height=\"1\""));
+    assert!(svg.contains("<rect x=\"8\" y=\"3.5\" width=\"4.5\" height=\"1\""));
+    assert!(svg.contains("<text x=\"16\" y=\"4\" textLength=\"8\""));
+    assert!(!svg.contains('─'));
+}
+
+#[test]
+fn display_grid_rasterize_glyphs() {
+    let grid = [['!', ' ']];
+
+    let image = grid.rasterize(&RasterOptions::default()).unwrap();
+
+    assert_eq!((image.width(), image.height()), (12, 10));
+    assert_eq!(image.get_pixel((2, 1)), Some((229, 229, 229)));
+    assert_eq!(image.get_pixel((2, 6)), Some((0, 0, 0)));
+    assert_eq!(image.get_pixel((2, 7)), Some((229, 229, 229)));
+    assert_eq!(image.get_pixel((1, 1)), Some((0, 0, 0)));
+    assert_eq!(image.get_pixel((12, 0)), None);
+}
+
+#[test]
+fn display_grid_rasterize_styles_and_scale() {
+    let style = Style::new()
+        .fg(Color::Rgb(1, 2, 3))
+        .bg(Color::Rgb(4, 5, 6))
+        .underline();
+    let grid = [[Styled::new('█', style), Styled::from('▀')]];
+    let options = RasterOptions {
+        scale: 2,
+        ..RasterOptions::default()
+    };
+
+    let image = grid.rasterize(&options).unwrap();
+
+    assert_eq!((image.width(), image.height()), (24, 20));
+    assert_eq!(image.get_pixel((0, 0)), Some((1, 2, 3)));
+    assert_eq!(image.get_pixel((11, 19)), Some((1, 2, 3)));
+    assert_eq!(image.get_pixel((12, 9)), Some((229, 229, 229)));
+    assert_eq!(image.get_pixel((12, 10)), Some((0, 0, 0)));
+}
+
+#[test]
+fn display_grid_rasterize_box_drawing_connects() {
+    let grid = [['─', '─']];
+
+    let image = grid.rasterize(&RasterOptions::default()).unwrap();
+
+    let row: Vec<_> = (0..12).map(|x| image.get_pixel((x, 4)).unwrap()).collect();
+    assert_eq!(row, vec![(229, 229, 229); 12]);
+}
+
+#[test]
+fn display_grid_rasterize_missing_glyph() {
+    let grid = [['é']];
+
+    let image = grid.rasterize(&RasterOptions::default()).unwrap();
+
+    assert_eq!(image.get_pixel((1, 1)), Some((229, 229, 229)));
+    assert_eq!(image.get_pixel((2, 2)), Some((0, 0, 0)));
+}
+
+#[test]
+#[cfg(feature = "png")]
+fn rgb_image_write_png() {
+    let image = RgbImage::new(3, 2, Color::Red);
+
+    let mut png = Vec::new();
+    image.write_png(&mut png).unwrap();
+
+    assert_eq!(&png[..8], b"\x89PNG\r\n\x1a\n");
+}
+
+#[test]
+fn grid_flat() {
+    let mut grid = Grid::new(2, 2, 0);
+
+    grid.set((1, 0), 1);
+
+    assert_eq!(grid.cells(), &[0, 1, 0, 0]);
+    assert_eq!(GridReader::get(&grid, (1, 0)), Some(&1));
+    assert_eq!(GridReader::get(&grid, (2, 0)), None);
+    assert_eq!(grid.rows().collect::<Vec<_>>(), vec![&[0, 1], &[0, 0]]);
+    assert_eq!(grid.to_string().unwrap(), "01\n00\n");
+}
+
+#[test]
+#[should_panic]
+fn grid_flat_out_of_bounds() {
+    let mut grid = Grid::new(2, 2, 0);
+    grid.set((2, 0), 1);
+}
+
+#[test]
+#[should_panic]
+fn grid_flat_from_cells_wrong_size() {
+    let _ = Grid::from_cells(2, 2, vec![0; 3]);
+}
+
+#[test]
+fn from_text_vec_ragged() {
+    let options = TextOptions {
+        pad: None,
+        tab_width: None,
+    };
+
+    let grid = Vec::<Vec<char>>::from_text_with("ab\r\n\nc\td\n", &options).unwrap();
+
+    assert_eq!(grid, vec![vec!['a', 'b'], vec![], vec!['c', '\t', 'd']]);
+}
+
+#[test]
+fn from_text_vec_padded() {
+    let grid = Vec::<Vec<char>>::from_text("ab\n\nc").unwrap();
+
+    assert_eq!(grid, vec![vec!['a', 'b'], vec![' ', ' '], vec!['c', ' ']]);
+}
+
+#[test]
+fn from_text_tab_expansion() {
+    let options = TextOptions {
+        tab_width: Some(4),
+        ..TextOptions::default()
+    };
+
+    let grid = Grid::from_text_with("\tx\nabcde\tf", &options).unwrap();
+
+    assert_eq!(grid.size(), (9, 2));
+    assert_eq!(grid.to_string().unwrap(), "    x    \nabcde   f\n");
+}
+
+#[test]
+fn from_text_array_round_trip() {
+    let grid = [['a', 'b', 'c'], ['d', 'e', 'f']];
+
+    let text = grid.to_string().unwrap();
+
+    assert_eq!(<[[char; 3]; 2]>::from_text(&text), Ok(grid));
+}
+
+#[test]
+fn from_text_array_padded() {
+    let grid = <[[char; 3]; 3]>::from_text("a\nbc").unwrap();
+
+    assert_eq!(grid, [['a', ' ', ' '], ['b', 'c', ' '], [' ', ' ', ' ']]);
+}
+
+#[test]
+fn from_text_array_size_mismatch() {
+    let error = <[[char; 2]; 2]>::from_text("abc\nd").unwrap_err();
+    assert_eq!(
+        error,
+        ParseGridError::SizeMismatch {
+            expected: (2, 2),
+            actual: (3, 2)
+        }
+    );
+    assert_eq!(
+        error.to_string(),
+        "expected a 2x2 grid, but the text is 3x2"
+    );
+
+    let options = TextOptions {
+        pad: None,
+        tab_width: None,
+    };
+    assert!(<[[char; 2]; 2]>::from_text_with("ab", &options).is_err());
+    assert!(<[[char; 2]; 2]>::from_text_with("ab\nc", &options).is_err());
+    assert!(<[[char; 2]; 2]>::from_text_with("ab\ncd", &options).is_ok());
+}
+
+#[test]
+fn from_text_grid_ragged_line() {
+    let options = TextOptions {
+        pad: None,
+        tab_width: None,
+    };
+
+    let error = Grid::from_text_with("abc\nd\nefg", &options).unwrap_err();
+
+    #[rustfmt::skip]
+    assert_eq!(error, ParseGridError::RaggedLine { line: 1, expected: 3, actual: 1 });
+    assert_eq!(
+        error.to_string(),
+        "expected line 1 to be 3 characters long, but it is 1"
+    );
+}
+
+#[test]
+fn from_str_grid() {
+    let grid: Grid<char> = "".parse().unwrap();
+
+    assert_eq!(grid.size(), (0, 0));
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn serde_grid_generic_round_trip() {
+    let grid = Grid::from_cells(
+        2,
+        1,
+        vec![Styled::new('a', Style::new().bold()), 'b'.into()],
+    );
+    let json = serde_json::to_string(&grid).unwrap();
+
+    assert_eq!(serde_json::from_str::<Grid<Styled>>(&json).unwrap(), grid);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn serde_grid_rejects_wrong_cell_count() {
+    let error =
+        serde_json::from_str::<Grid<u8>>(r#"{"width":2,"height":2,"cells":[1,2,3]}"#).unwrap_err();
+
+    assert!(error.to_string().contains("width * height"));
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn serde_grid_compact_rejects_ragged_rows() {
+    let mut deserializer =
+        serde_json::Deserializer::from_str(r#"{"width":2,"height":2,"rows":["ab","c"]}"#);
+    let error = compact::deserialize(&mut deserializer).unwrap_err();
+
+    assert!(error.to_string().contains("row 1"));
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn serde_grid_compact_rejects_huge_dimensions() {
+    #[derive(Debug, serde::Deserialize)]
+    struct Level {
+        #[serde(with = "compact")]
+        #[allow(dead_code)]
+        map: Grid<char>,
+    }
+
+    let error =
+        serde_json::from_str::<Level>(r#"{"map":{"width":1099511627776,"height":1,"rows":["a"]}}"#)
+            .err()
+            .unwrap();
+
+    assert!(error.to_string().contains("row 0"));
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn serde_sprites_round_trip() {
+    let line: Line<char> =
+        serde_json::from_str(&serde_json::to_string(&Line::vertical(2, '║')).unwrap()).unwrap();
+    assert_eq!(line.orientation(), Orientation::Vertical);
+
+    let json = serde_json::to_string(&BorderRect::new(
+        3,
+        2,
+        ['╔', '═', '╗', '║', '║', '╚', '═', '╝'],
+    ))
+    .unwrap();
+    let rect: BorderRect<char> = serde_json::from_str(&json).unwrap();
+    let fill: FillRect<char> =
+        serde_json::from_str(r#"{"width":1,"height":1,"render":"X"}"#).unwrap();
+
+    let mut grid = [[' '; 3]; 3];
+    rect.draw_to((0, 0), &mut grid);
+    line.draw_to((0, 1), &mut grid);
+    fill.draw_to((1, 2), &mut grid);
+
+    #[rustfmt::skip]
+    assert_eq!(grid, [
+        ['╔', '═', '╗'],
+        ['║', '═', '╝'],
+        ['║', 'X', ' '],
+    ]);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn serde_border_rect_rejects_small_size() {
+    let json = r#"{"width":1,"height":2,"render":["a","b","c","d","e","f","g","h"]}"#;
+
+    assert!(serde_json::from_str::<BorderRect<char>>(json).is_err());
+}
+
+/// Gzips raw `.xp` data, as REXPaint does.
+#[cfg(feature = "xp")]
+fn gzip(bytes: &[u8]) -> Vec<u8> {
+    use std::io::Write;
+
+    let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::fast());
+    encoder.write_all(bytes).unwrap();
+    encoder.finish().unwrap()
+}
+
+#[cfg(feature = "xp")]
+#[test]
+fn xp_read_column_major_cp437() {
+    let mut bytes = Vec::new();
+    bytes.extend((-1i32).to_le_bytes());
+    bytes.extend(1i32.to_le_bytes());
+    bytes.extend(2i32.to_le_bytes());
+    bytes.extend(1i32.to_le_bytes());
+    bytes.extend(201u32.to_le_bytes());
+    bytes.extend([1, 2, 3, 4, 5, 6]);
+    bytes.extend(0u32.to_le_bytes());
+    bytes.extend([7, 8, 9, 255, 0, 255]);
+
+    let image = XpImage::read(&mut gzip(&bytes).as_slice()).unwrap();
+    let cells = image.layer(0).unwrap().cells();
+
+    assert_eq!(image.size(), (2, 1));
+    assert_eq!(
+        cells[0],
+        Styled::new(
+            '╔',
+            Style::new().fg(Color::Rgb(1, 2, 3)).bg(Color::Rgb(4, 5, 6))
+        )
+    );
+    assert_eq!(
+        cells[1],
+        Styled::new(' ', Style::new().fg(Color::Rgb(7, 8, 9)))
+    );
+}
+
+#[cfg(feature = "xp")]
+#[test]
+fn xp_round_trip_layers() {
+    let style = Style::new()
+        .fg(Color::Rgb(0, 255, 0))
+        .bg(Color::Rgb(0, 0, 0));
+    let mut image = XpImage::new(3, 2, 1);
+    BorderRect::new(
+        3,
+        2,
+        ['╔', '═', '╗', '║', '║', '╚', '═', '╝'].map(|c| Styled::new(c, style)),
+    )
+    .draw_to((0, 0), image.layer_mut(0).unwrap());
+
+    let mut top = Grid::new(3, 2, Styled::from(' '));
+    top.set((1, 1), Styled::new('é', style));
+    image.push_layer(top);
+
+    let mut file = Vec::new();
+    image.write(&mut file).unwrap();
+    let read = XpImage::read(&mut file.as_slice()).unwrap();
+
+    assert_eq!(read.layers()[0], image.layers()[0]);
+    assert_eq!(
+        read.flatten().to_string().unwrap(),
+        image.flatten().to_string().unwrap()
+    );
+    assert_eq!(
+        read.flatten()
+            .rows()
+            .map(|row| row.iter().map(|c| c.value).collect::<String>())
+            .collect::<Vec<_>>(),
+        ["╔═╗", "╚é╝"]
+    );
+}
+
+#[cfg(feature = "xp")]
+#[test]
+fn xp_write_replaces_unknown_glyphs() {
+    let mut image = XpImage::new(1, 1, 1);
+    image.layer_mut(0).unwrap().set((0, 0), Styled::from('€'));
+
+    let mut file = Vec::new();
+    image.write(&mut file).unwrap();
+    let read = XpImage::read(&mut file.as_slice()).unwrap();
+
+    assert_eq!(read.layers()[0].cells()[0].value, '?');
+}
+
+#[cfg(feature = "xp")]
+#[test]
+fn xp_read_rejects_truncated_file() {
+    let mut bytes = Vec::new();
+    bytes.extend((-1i32).to_le_bytes());
+    bytes.extend(1i32.to_le_bytes());
+    bytes.extend(1000i32.to_le_bytes());
+    bytes.extend(1000i32.to_le_bytes());
+
+    let error = XpImage::read(&mut gzip(&bytes).as_slice()).unwrap_err();
+
+    assert_eq!(error.kind(), std::io::ErrorKind::InvalidData);
+}
+
+#[test]
+fn asciicast_recorder_writes_header_and_events() {
+    let header = Header {
+        timestamp: Some(1_700_000_000),
+        title: Some("\"Demo\"".to_string()),
+        ..Header::new(2, 2)
+    };
+    let mut recorder = Recorder::new(Vec::new(), &header).unwrap();
+    recorder
+        .frame(Duration::from_micros(1_500_000), &[['a', 'b'], ['c', 'd']])
+        .unwrap();
+
+    assert_eq!(
+        String::from_utf8(recorder.into_inner()).unwrap(),
+        concat!(
+            r#"{"version": 2, "width": 2, "height": 2, "#,
+            r#""timestamp": 1700000000, "title": "\"Demo\""}"#,
+            "\n",
+            r#"[1.500000, "o", "\u001b[2J\u001b[Hab\r\ncd"]"#,
+            "\n",
+        )
+    );
+}
+
+#[test]
+fn asciicast_recorder_rejects_out_of_order_frames() {
+    let mut recorder = Recorder::new(Vec::new(), &Header::new(1, 1)).unwrap();
+    recorder.frame(Duration::from_secs(1), &[['a']]).unwrap();
+
+    assert!(recorder.frame(Duration::ZERO, &[['b']]).is_err());
+}
+
+#[test]
+fn asciicast_round_trip_styled_frames() {
+    let red = Style::new().fg(Color::Red);
+    let mut grid = Grid::new(3, 2, Styled::from(' '));
+    let mut recorder = Recorder::new(Vec::new(), &Header::new(3, 2)).unwrap();
+    recorder.frame(Duration::ZERO, &grid).unwrap();
+    grid.set((2, 1), Styled::new('X', red));
+    recorder.frame(Duration::from_millis(40), &grid).unwrap();
+
+    let file = recorder.into_inner();
+    let recording = Recording::read(&mut file.as_slice()).unwrap();
+
+    assert_eq!(recording.header, Header::new(3, 2));
+    assert_eq!(recording.frames.len(), 2);
+    assert_eq!(recording.frames[0].text(), "   \n   \n");
+    assert_eq!(recording.frames[1].screen, grid);
+}
+
+#[test]
+fn asciicast_replays_cursor_movement() {
+    let file = concat!(
+        r#"{"version": 2, "width": 4, "height": 2, "env": {"TERM": "xterm"}}"#,
+        "\n",
+        r#"[0.1, "o", "abcd\r\nefgh"]"#,
+        "\n",
+        r#"[0.2, "i", "q"]"#,
+        "\n",
+        r#"[0.3, "o", "\u001b[1;2H\u001b[K\u001b[2;3H\u001b[1D\u001b[32m☺"]"#,
+        "\n",
+        r#"[0.4, "o", "\r\nij"]"#,
+        "\n",
+    );
+
+    let recording = Recording::read(&mut file.as_bytes()).unwrap();
+    let frames = &recording.frames;
+
+    assert_eq!(frames.len(), 3);
+    assert_eq!(frames[0].text(), "abcd\nefgh\n");
+    assert_eq!(frames[1].time, Duration::from_millis(300));
+    assert_eq!(frames[1].text(), "a   \ne☺gh\n");
+    assert_eq!(
+        frames[1].screen.get((1, 1)),
+        Some(&Styled::new('☺', Style::new().fg(Color::Green)))
+    );
+    assert_eq!(frames[2].text(), "e☺gh\nij  \n");
+}
+
+#[test]
+fn asciicast_read_rejects_other_versions() {
+    let file = r#"{"version": 1, "width": 4, "height": 2}"#;
+
+    assert!(Recording::read(&mut file.as_bytes()).is_err());
+}
+
+#[test]
+fn asciicast_read_rejects_huge_terminal_sizes() {
+    for size in [4_294_967_296_u64, 1_000_000] {
+        let file = format!(r#"{{"version": 2, "width": {size}, "height": {size}}}"#);
+        let error = Recording::read(&mut file.as_bytes()).unwrap_err();
+
+        assert_eq!(error.kind(), std::io::ErrorKind::InvalidData);
+    }
+}
+
+#[test]
+fn asciicast_read_rejects_deeply_nested_header() {
+    let file = "[".repeat(1_000_000);
+    let error = Recording::read(&mut file.as_bytes()).unwrap_err();
+
+    assert_eq!(error.kind(), std::io::ErrorKind::InvalidData);
+}
+
+#[test]
+fn json_parse_values() {
+    use json::Value;
+
+    assert_eq!(
+        json::parse(r#" {"a": [1, -2.5e1, true, false, null], "b": {}} "#),
+        Some(Value::Object(vec![
+            (
+                "a".to_string(),
+                Value::Array(vec![
+                    Value::Number(1.0),
+                    Value::Number(-25.0),
+                    Value::Bool(true),
+                    Value::Bool(false),
+                    Value::Null,
+                ])
+            ),
+            ("b".to_string(), Value::Object(vec![])),
+        ]))
+    );
+}
+
+#[test]
+fn json_parse_string_escapes() {
+    assert_eq!(
+        json::parse(r#""\"\\\/\b\f\n\r\t\u0041\u263a""#)
+            .as_ref()
+            .and_then(json::Value::as_str),
+        Some("\"\\/\x08\x0c\n\r\tA☺")
+    );
+}
+
+#[test]
+fn json_parse_surrogate_pairs() {
+    assert_eq!(
+        json::parse(r#""\ud83d\ude00""#)
+            .as_ref()
+            .and_then(json::Value::as_str),
+        Some("😀")
+    );
+    assert_eq!(json::parse(r#""\ud83d""#), None);
+    assert_eq!(json::parse(r#""\ud83d\u0041""#), None);
+}
+
+#[test]
+fn json_parse_rejects_malformed_input() {
+    for text in [
+        "",
+        "[1,",
+        "[1 2]",
+        "{\"a\" 1}",
+        "{1: 2}",
+        "tru",
+        "\"open",
+        "1.2.3",
+        "[] []",
+        "\"\\u12\"",
+    ] {
+        assert_eq!(json::parse(text), None, "{text:?}");
+    }
+}
+
+#[test]
+fn json_parse_limits_nesting() {
+    let nested = |depth| format!("{}{}", "[".repeat(depth), "]".repeat(depth));
+
+    assert!(json::parse(&nested(128)).is_some());
+    assert_eq!(json::parse(&nested(129)), None);
+}
+
+#[test]
+fn json_write_string_escapes() {
+    let mut out = String::new();
+    json::write_string(&mut out, "\"a\\b\n\r\t\x1b☺");
+
+    assert_eq!(out, r#""\"a\\b\n\r\t\u001b☺""#);
+    assert_eq!(
+        json::parse(&out).as_ref().and_then(json::Value::as_str),
+        Some("\"a\\b\n\r\t\x1b☺")
+    );
+}
+
+#[test]
+fn grid_diff_marks_mismatches_side_by_side() {
+    let left = Grid::from_cells(3, 1, vec!['a', 'b', 'c']);
+    let right = vec![vec!['a', 'x', 'c', 'd'], vec!['e']];
+
+    assert_eq!(
+        grid_diff(&left, &right).unwrap(),
+        concat!(
+            "grids differ at 3 cell(s), as (x, y): (1, 0), (3, 0), (0, 1)\n",
+            "left │ right │ diff\n",
+            "abc  │ axcd  │  ^ ^\n",
+            "     │ e     │ ^\n",
+        )
+    );
+}
+
+#[test]
+fn grid_diff_reports_style_only_mismatches() {
+    let left = [[Styled::from('a')]];
+    let right = [[Styled::new('a', Style::new().bold())]];
+
+    assert!(grid_diff(&left, &right)
+        .unwrap()
+        .starts_with("grids differ at 1 cell(s), as (x, y): (0, 0)*\n"));
+    assert_eq!(grid_diff(&left, &[['a']]), None);
+}
+
+#[test]
+#[should_panic(expected = "grids differ")]
+fn assert_grid_eq_panics_with_diff() {
+    assert_grid_eq!([['a', 'b']], [['a', 'c']]);
+}
+
+#[test]
+fn assert_snapshot_compares() {
+    let path = std::env::temp_dir().join(format!("grux-snapshot-{}.txt", std::process::id()));
+    std::fs::write(&path, "╔═╗\r\n╚═╝\r\n").unwrap();
+
+    assert_snapshot(&path, &[['╔', '═', '╗'], ['╚', '═', '╝']]);
+
+    let result = std::panic::catch_unwind(|| assert_snapshot(&path, &[['╔', '═', '╗']]));
+    std::fs::remove_file(&path).unwrap();
+
+    assert!(result.is_err());
+}
+
+#[test]
+fn check_snapshot_updates() {
+    let dir = std::env::temp_dir().join(format!("grux-snapshots-{}", std::process::id()));
+    let path = dir.join("nested").join("box.txt");
+    let grid = [['╔', '═', '╗'], ['╚', '═', '╝']];
+
+    testing::check_snapshot(&path, &grid, true);
+    let written = std::fs::read_to_string(&path).unwrap();
+    testing::check_snapshot(&path, &[['x']], true);
+    let updated = std::fs::read_to_string(&path).unwrap();
+    std::fs::remove_dir_all(&dir).unwrap();
+
+    assert_eq!(written, "╔═╗\n╚═╝\n");
+    assert_eq!(updated, "x\n");
+}
+
+/// Writes a grid with the given options, as a string.
+fn write_with_to_string(grid: &impl DisplayGrid, options: &DisplayOptions) -> String {
+    let mut output = Vec::new();
+    grid.write_with(&mut output, options).unwrap();
+    String::from_utf8(output).unwrap()
+}
+
+#[test]
+fn display_options_consistent_across_grids() {
+    let mut string = String::new();
+    let mut vec = vec![vec![' ']; 2];
+    let mut array = [[' '; 2]; 2];
+    let mut grid = Grid::new(2, 2, ' ');
+    for position in [(0, 0), (1, 1)] {
+        string.set(position, 'X');
+        vec.set(position, 'X');
+        array.set(position, 'X');
+        grid.set(position, 'X');
+    }
+    let options = DisplayOptions {
+        line_ending: LineEnding::CrLf,
+        trailing_newline: false,
+        trim_trailing: true,
+        pad: Some(' '),
+        row_prefix: "[".to_string(),
+        row_suffix: "]".to_string(),
+    };
+
+    assert_eq!(DisplayGrid::to_string(&string).unwrap(), "X\n X\n");
+    for output in [
+        write_with_to_string(&string, &options),
+        write_with_to_string(&vec, &options),
+        write_with_to_string(&array, &options),
+        write_with_to_string(&grid, &options),
+        write_with_to_string(&Wrapping::new(grid.clone(), 2, 2), &options),
+    ] {
+        assert_eq!(output, "[X]\r\n[ X]");
+    }
+}
+
+#[test]
+fn display_options_pad_ragged_rows() {
+    let grid = vec![vec![1, 2, 3], vec![], vec![4]];
+    let options = DisplayOptions {
+        pad: Some('.'),
+        ..DisplayOptions::default()
+    };
+
+    assert_eq!(write_with_to_string(&grid, &options), "123\n...\n4..\n");
+}
+
+#[test]
+fn display_options_sparse_grid() {
+    let mut grid = SparseGrid::new(' ');
+    grid.insert((-1, 0), 'a');
+    grid.insert((1, 1), 'b');
+    let options = DisplayOptions {
+        trim_trailing: true,
+        ..DisplayOptions::default()
+    };
+
+    assert_eq!(write_with_to_string(&grid, &options), "a\n  b\n");
+}
+
+#[test]
+fn display_options_default_impl_keeps_styles() {
+    struct MyGrid;
+
+    impl DisplayGrid for MyGrid {
+        fn write_to(&self, stream: &mut impl std::io::Write) -> std::io::Result<()> {
+            writeln!(stream, "a\x1b[41m \x1b[0m  ")?;
+            writeln!(stream, "b")
+        }
+    }
+    let options = DisplayOptions {
+        trim_trailing: true,
+        pad: Some('-'),
+        ..DisplayOptions::default()
+    };
+
+    assert_eq!(
+        write_with_to_string(&MyGrid, &options),
+        "a\x1b[41m \x1b[0m\nb---\n"
+    );
+}
+
+#[test]
+fn display_adapter_matches_write_to() {
+    let mut sparse = SparseGrid::new('.');
+    sparse.insert((-1, -1), 'a');
+    sparse.insert((1, 0), 'b');
+    let grid = Grid::from_cells(
+        2,
+        1,
+        vec![Styled::new('x', Style::new().bold()), 'y'.into()],
+    );
+
+    assert_eq!(format!("{}", sparse.display()), sparse.to_string().unwrap());
+    assert_eq!(format!("{}", grid.display()), grid.to_string().unwrap());
+    assert_eq!(
+        format!("{}", History::new(vec![vec![1, 2], vec![3]]).display()),
+        "12\n3\n"
+    );
+    assert_eq!(format!("{:?}", String::from("ab\nc").display()), "ab\nc\n");
+    assert_eq!(format!("{}", SparseGrid::new(' ').display()), "");
+}
+
+#[test]
+fn format_grid_propagates_errors() {
+    struct Full;
+
+    impl std::fmt::Write for Full {
+        fn write_str(&mut self, _: &str) -> std::fmt::Result {
+            Err(std::fmt::Error)
+        }
+    }
+
+    assert!([['a']].write_fmt_to(&mut Full).is_err());
+    assert!(Grid::new(1, 1, 'a').write_fmt_to(&mut Full).is_err());
+    assert!([['a'; 0]; 0].write_fmt_to(&mut Full).is_ok());
+}
+
+#[test]
+fn braille_canvas_pixels() {
+    let mut canvas = BrailleCanvas::new(1, 1);
+    assert_eq!(canvas.pixel_size(), (2, 4));
+
+    for y in 0..4 {
+        canvas.set_pixel((0, y));
+    }
+    canvas.set_pixel((1, 3));
+    canvas.set_pixel((2, 0));
+    canvas.unset_pixel((0, 1));
+
+    assert!(canvas.get_pixel((1, 3)));
+    assert!(!canvas.get_pixel((0, 1)));
+    assert!(!canvas.get_pixel((2, 0)));
+    assert_eq!(canvas.cell((0, 0)), Some('⣅'));
+    assert_eq!(canvas.cell((1, 0)), None);
+
+    canvas.clear();
+    assert_eq!(canvas.cell((0, 0)), Some('\u{2800}'));
+}
+
+#[test]
+fn braille_canvas_circle() {
+    let mut canvas = BrailleCanvas::new(3, 2);
+    canvas.circle((2, 4), 2);
+
+    let mut grid = [['.'; 4]; 2];
+    canvas.draw_to((1, 0), &mut grid);
+
+    #[rustfmt::skip]
+    assert_eq!(grid, [
+        ['.', '⡠', '⠤', '⡀'],
+        ['.', '⠣', '⠤', '⠃'],
+    ]);
+}
+
+#[test]
+fn braille_canvas_clips_shapes() {
+    let mut canvas = BrailleCanvas::new(1, 1);
+    canvas.line((1, 0), (100, 100));
+    canvas.circle((0, 0), 1);
+
+    assert!(canvas.get_pixel((1, 0)));
+    assert!(canvas.get_pixel((0, 1)));
+    assert!(!canvas.get_pixel((0, 0)));
+    assert!(!canvas.get_pixel((1, 1)));
+}
+
+#[test]
+fn block_canvas_quadrants() {
+    let mut canvas = BlockCanvas::new(16, 1, BlockResolution::Quadrant);
+    assert_eq!(canvas.pixel_size(), (32, 2));
+
+    for mask in 0..16 {
+        for (bit, (dx, dy)) in [(0, 0), (1, 0), (0, 1), (1, 1)].into_iter().enumerate() {
+            if mask & (1 << bit) != 0 {
+                canvas.set_pixel((mask * 2 + dx, dy), Color::White);
+            }
+        }
+    }
+    canvas.set_pixel((32, 0), Color::White);
+
+    let mut grid = [[' '; 16]; 1];
+    canvas.draw_to((0, 0), &mut grid);
+
+    assert_eq!(String::from_iter(grid[0]), " ▘▝▀▖▌▞▛▗▚▐▜▄▙▟█");
+    assert_eq!(canvas.get_pixel((32, 0)), None);
+}
+
+#[test]
+fn block_canvas_styled_colors() {
+    let mut canvas = BlockCanvas::new(2, 1, BlockResolution::Quadrant);
+
+    // Three colors: the dark red pixel is closest to red.
+    canvas.set_pixel((0, 0), Color::Blue);
+    canvas.set_pixel((1, 0), Color::Rgb(255, 0, 0));
+    canvas.set_pixel((0, 1), Color::Rgb(128, 0, 0));
+    canvas.set_pixel((1, 1), Color::Rgb(255, 0, 0));
+
+    // An unset pixel is always the background.
+    canvas.set_pixel((2, 0), Color::Green);
+    canvas.set_pixel((3, 0), Color::Green);
+    canvas.set_pixel((2, 1), Color::Green);
+
+    let red_on_blue = Style::new().fg(Color::Rgb(255, 0, 0)).bg(Color::Blue);
+    let green = Style::new().fg(Color::Green);
+    assert_eq!(
+        canvas.styled_cell((0, 0)),
+        Some(Styled::new('▟', red_on_blue))
+    );
+    assert_eq!(canvas.styled_cell((1, 0)), Some(Styled::new('▛', green)));
+    assert_eq!(canvas.styled_cell((2, 0)), None);
+
+    canvas.unset_pixel((0, 0));
+    assert_eq!(
+        canvas.styled_cell((0, 0)),
+        Some(Styled::new('▟', Style::new().fg(Color::Rgb(255, 0, 0))))
+    );
+}
+
+#[test]
+fn block_canvas_styled_three_colors() {
+    let mut canvas = BlockCanvas::new(3, 1, BlockResolution::Quadrant);
+
+    // A tie: red and green are kept, since they come first, and blue is closer to green.
+    canvas.set_pixel((0, 0), Color::Rgb(255, 0, 0));
+    canvas.set_pixel((1, 0), Color::Rgb(0, 255, 0));
+    canvas.set_pixel((0, 1), Color::Rgb(0, 128, 255));
+
+    // Unset ranks second: the background is the default, and blue is drawn as red.
+    canvas.set_pixel((2, 0), Color::Rgb(255, 0, 0));
+    canvas.set_pixel((3, 0), Color::Rgb(255, 0, 0));
+    canvas.set_pixel((3, 1), Color::Rgb(0, 0, 255));
+
+    // Unset ranks first: blue ranks above red (it comes first), and is used for every set pixel.
+    canvas.set_pixel((4, 0), Color::Rgb(0, 0, 255));
+    canvas.set_pixel((5, 0), Color::Rgb(255, 0, 0));
+
+    let red_on_green = Style::new()
+        .fg(Color::Rgb(255, 0, 0))
+        .bg(Color::Rgb(0, 255, 0));
+    assert_eq!(
+        canvas.styled_cell((0, 0)),
+        Some(Styled::new('▘', red_on_green))
+    );
+    let red = Style::new().fg(Color::Rgb(255, 0, 0));
+    assert_eq!(canvas.styled_cell((1, 0)), Some(Styled::new('▜', red)));
+    let blue = Style::new().fg(Color::Rgb(0, 0, 255));
+    assert_eq!(canvas.styled_cell((2, 0)), Some(Styled::new('▀', blue)));
+}
+
+#[test]
+fn block_canvas_from_pixels() {
+    let red = Some(Color::Red);
+    #[rustfmt::skip]
+    let pixels = Grid::from_cells(3, 3, vec![
+        red,  None, red,
+        None, red,  None,
+        red,  red,  red,
+    ]);
+
+    let canvas = BlockCanvas::from_pixels(&pixels, BlockResolution::Quadrant);
+    assert_eq!((canvas.width(), canvas.height()), (2, 2));
+    assert_eq!(canvas.resolution(), BlockResolution::Quadrant);
+
+    let mut grid = [[' '; 2]; 2];
+    canvas.draw_to((0, 0), &mut grid);
+
+    #[rustfmt::skip]
+    assert_eq!(grid, [
+        ['▚', '▘'],
+        ['▀', '▘'],
+    ]);
+
+    let canvas = BlockCanvas::from_pixels(&pixels, BlockResolution::Half);
+    let mut grid = [[' '; 3]; 2];
+    canvas.draw_to((0, 0), &mut grid);
+
+    #[rustfmt::skip]
+    assert_eq!(grid, [
+        ['▀', '▄', '▀'],
+        ['▀', '▀', '▀'],
+    ]);
+}
+
+#[test]
+fn sparkline_scales_to_size() {
+    let sparkline = Sparkline::new([0.0, 2.0, 8.0, 8.0, f64::NAN, 4.0]).with_size(3, 1);
+    assert_eq!(sparkline.data().len(), 6);
+
+    let mut grid = [['.'; 3]; 1];
+    sparkline.draw_to((0, 0), &mut grid);
+    assert_eq!(grid, [['▂', '█', '▅']]);
+
+    // A column with only missing values is blank, and values outside the range are clamped.
+    let sparkline = Sparkline::new([f64::NAN, -5.0, 5.0, 50.0]).with_range(0.0, 10.0);
+    let mut grid = [['.'; 4]; 1];
+    sparkline.draw_to((0, 0), &mut grid);
+    assert_eq!(grid, [[' ', '▁', '▅', '█']]);
+}
+
+#[test]
+fn sparkline_of_equal_values() {
+    let sparkline = Sparkline::new(vec![3.0; 4]).with_size(4, 2);
+
+    let mut grid = [['.'; 4]; 2];
+    sparkline.draw_to((0, 0), &mut grid);
+
+    #[rustfmt::skip]
+    assert_eq!(grid, [
+        [' ', ' ', ' ', ' '],
+        ['▁', '▁', '▁', '▁'],
+    ]);
+}
+
+#[test]
+fn bar_chart_vertical() {
+    let chart = BarChart::new(Orientation::Vertical, 6, 3)
+        .with_bar("one", 1.0)
+        .with_bar("", 0.75)
+        .with_bar("", -1.0)
+        .with_bar("four", 1.0)
+        .with_max(2.0);
+    assert_eq!(chart.orientation(), Orientation::Vertical);
+    assert_eq!(chart.bars().len(), 4);
+
+    let mut grid = [['.'; 6]; 3];
+    chart.draw_to((0, 0), &mut grid);
+
+    // The fourth bar does not fit.
+    #[rustfmt::skip]
+    assert_eq!(grid, [
+        [' ', ' ', ' ', ' ', ' ', ' '],
+        ['█', ' ', '▆', ' ', ' ', ' '],
+        ['o', ' ', ' ', ' ', ' ', ' '],
+    ]);
+}
+
+#[test]
+fn bar_chart_horizontal() {
+    let chart = BarChart::new(Orientation::Horizontal, 4, 4)
+        .with_bar("", 3.0)
+        .with_bar("", 1.0)
+        .with_bar_width(2, 0);
+
+    let mut grid = [['.'; 4]; 4];
+    chart.draw_to((0, 0), &mut grid);
+
+    #[rustfmt::skip]
+    assert_eq!(grid, [
+        ['█', '█', '█', '█'],
+        ['█', '█', '█', '█'],
+        ['█', '▍', ' ', ' '],
+        ['█', '▍', ' ', ' '],
+    ]);
+}
+
+#[test]
+fn plot_braille_lines_with_legend() {
+    let plot = Plot::new(14, 6)
+        .with_series(
+            Series::new("up", [(0.0, -1.0), (4.0, 1.0)])
+                .with_marker(Marker::Braille)
+                .with_lines(true),
+        )
+        .with_series(Series::new("pt", [(2.0, 0.0)]).with_marker(Marker::Char('o')))
+        .with_series(Series::new("", [(3.0, 0.5)]));
+    assert_eq!(plot.series().len(), 3);
+    assert_eq!(plot.ranges(), ((0.0, 4.0), (-1.0, 1.0)));
+
+    let mut grid = [[' '; 14]; 6];
+    plot.draw_to((0, 0), &mut grid);
+
+    assert_eq!(
+        DisplayGrid::to_string(&grid).unwrap(),
+        concat!(
+            " 1┤       ⣿ up\n",
+            " 0┤     o⠤o pt\n",
+            "  │  ⢀⡠⠒⠁     \n",
+            "-1┤⡠⠔⠁        \n",
+            "  └┬────┬────┬\n",
+            "   0    2    4\n",
+        )
+    );
+}
+
+#[test]
+fn plot_clips_points_outside_ranges() {
+    let plot = Plot::new(6, 4)
+        .with_series(
+            Series::new("", [(-5.0, 0.0), (0.0, 0.0), (5.0, 0.0), (f64::NAN, 0.0)])
+                .with_lines(true),
+        )
+        .with_x_range(0.0, 4.0)
+        .with_y_range(0.0, 1.0)
+        .with_ticks(2, 2)
+        .with_legend(false);
+
+    let mut grid = [['.'; 6]; 4];
+    plot.draw_to((0, 0), &mut grid);
+
+    assert_eq!(
+        DisplayGrid::to_string(&grid).unwrap(),
+        concat!("1┤    \n", "0┤****\n", " └┬──┬\n", "  0  4\n",)
+    );
+}
+
+#[test]
+fn plot_skips_points_far_outside_ranges() {
+    let plot = Plot::new(20, 8)
+        .with_y_range(0.0, 1.0)
+        .with_series(Series::new("a", [(0.5, -1e300)]))
+        .with_legend(false);
+    let mut grid = [[' '; 20]; 8];
+    plot.draw_to((0, 0), &mut grid);
+
+    assert!(grid.iter().flatten().all(|&c| c != '*'));
+}
+
+#[test]
+fn plot_clips_long_lines_to_ranges() {
+    let plot = Plot::new(6, 4)
+        .with_x_range(0.0, 1.0)
+        .with_y_range(0.0, 1.0)
+        .with_ticks(2, 2)
+        .with_legend(false)
+        .with_series(Series::new("a", [(0.0, 0.0), (1e15, 1.0)]).with_lines(true));
+    let mut grid = [[' '; 6]; 4];
+    plot.draw_to((0, 0), &mut grid);
+
+    assert_eq!(
+        DisplayGrid::to_string(&grid).unwrap(),
+        concat!("1┤    \n", "0┤****\n", " └┬──┬\n", "  0  1\n")
+    );
+}
+
+#[test]
+fn plot_tiny_sizes_do_not_panic() {
+    for (width, height) in [(10, 0), (10, 1), (10, 2), (0, 10), (1, 3), (3, 3)] {
+        let plot = Plot::new(width, height).with_series(Series::new("s", [(0.0, 0.0), (1.0, 1.0)]));
+        let mut grid = vec![vec!['.'; width + 1]; height + 1];
+        plot.draw_to((0, 0), &mut grid);
+
+        if height <= 2 {
+            let expected = (0..=height)
+                .map(|y| (0..=width).map(move |x| if x < width && y < height { ' ' } else { '.' }))
+                .map(String::from_iter)
+                .collect::<Vec<_>>();
+            assert_eq!(
+                grid.iter().map(String::from_iter).collect::<Vec<_>>(),
+                expected
+            );
+        }
+    }
+}
+
+#[test]
+fn progress_bar_partial_blocks() {
+    let draw = |bar: &ProgressBar| {
+        let mut grid = [['.'; 4]; 1];
+        bar.draw_to((0, 0), &mut grid);
+        String::from_iter(grid[0])
+    };
+
+    let mut bar = ProgressBar::new(4, 0.0);
+    assert_eq!(draw(&bar), "    ");
+    for (value, expected) in [
+        (1.0 / 32.0, "▏   "),
+        (0.5, "██  "),
+        (0.7, "██▊ "),
+        (1.5, "████"),
+        (-1.0, "    "),
+        (f64::NAN, "    "),
+    ] {
+        bar.set_value(value);
+        assert_eq!(draw(&bar), expected, "value {}", value);
+    }
+    assert!(bar.value().is_nan());
+
+    // Other glyphs are rounded to whole cells.
+    let bar = ProgressBar::new(4, 0.7).with_glyphs('#', '.');
+    assert_eq!(draw(&bar), "###.");
+}
+
+#[test]
+fn progress_bar_text_and_ends() {
+    let bar = ProgressBar::new(8, 1.0)
+        .with_label("downloading")
+        .with_ends('|', '|');
+
+    let mut grid = [[' '; 8]; 1];
+    bar.draw_to((0, 0), &mut grid);
+    assert_eq!(String::from_iter(grid[0]), "|downlo|");
+
+    // Too narrow for its ends.
+    let bar = ProgressBar::new(1, 1.0).with_ends('[', ']');
+    let mut grid = [['.'; 1]; 1];
+    bar.draw_to((0, 0), &mut grid);
+    assert_eq!(grid, [['.']]);
+}
+
+#[test]
+fn gauge_fills_arc() {
+    let draw = |gauge: &Gauge| {
+        let mut grid = [['.'; 6]; 3];
+        gauge.draw_to((0, 0), &mut grid);
+        DisplayGrid::to_string(&grid).unwrap()
+    };
+
+    let mut gauge = Gauge::new(6, 3, 0.0).with_label("cpu");
+    assert_eq!(draw(&gauge), " ⡠⠤⠤⢄ \n⡎    ⢱\n cpu  \n");
+
+    gauge.set_value(1.0);
+    assert_eq!(gauge.value(), 1.0);
+    assert_eq!(draw(&gauge), " ⣠⣤⣤⣄ \n⣾⠏  ⠹⣷\n cpu  \n");
+
+    // Without text, the arc is on the bottom row, and fits the width.
+    let gauge = Gauge::new(4, 2, 1.0);
+    let mut grid = [['.'; 4]; 2];
+    gauge.draw_to((0, 0), &mut grid);
+    assert_eq!(DisplayGrid::to_string(&grid).unwrap(), "    \n⣼⠋⠙⣧\n");
+}
+
+#[test]
+fn table_double_border_junctions() {
+    let table = Table::new(["a", "b", "c"])
+        .with_row(["1"])
+        .with_border(['╔', '═', '╗', '║', '║', '╚', '═', '╝'])
+        .with_padding(0);
+    assert_eq!(table.headers().len(), 3);
+    assert_eq!(table.rows().len(), 1);
+    assert_eq!((table.width(), table.height()), (7, 5));
+
+    let mut grid = vec![vec!['.'; 7]; 5];
+    table.draw_to((0, 0), &mut grid);
+
+    assert_eq!(
+        DisplayGrid::to_string(&grid).unwrap(),
+        concat!(
+            "╔═╦═╦═╗\n",
+            "║a║b║c║\n",
+            "╠═╬═╬═╣\n",
+            "║1║ ║ ║\n",
+            "╚═╩═╩═╝\n",
+        )
+    );
+}
+
+#[test]
+fn table_without_headers() {
+    let table = Table::new(Vec::<String>::new())
+        .with_row(["x"])
+        .with_row(["y", "z"]);
+
+    let mut grid = vec![vec!['.'; table.width()]; table.height()];
+    table.draw_to((0, 0), &mut grid);
+
+    assert_eq!(
+        DisplayGrid::to_string(&grid).unwrap(),
+        concat!("┌───┬───┐\n", "│ x │   │\n", "│ y │ z │\n", "└───┴───┘\n",)
+    );
+}
+
+#[test]
+fn table_column_widths() {
+    let table = Table::new(["fixed", "auto", "share", "double"])
+        .with_row(["abcdef", "a", "x", "y"])
+        .with_column_width(0, ColumnWidth::Fixed(4))
+        .with_column_width(1, ColumnWidth::Auto { min: 3, max: 3 })
+        .with_column_width(2, ColumnWidth::Proportional(1))
+        .with_column_width(3, ColumnWidth::Proportional(2))
+        .with_alignment(1, Alignment::Center)
+        .with_alignment(3, Alignment::Right)
+        .with_width(30)
+        .with_padding(0);
+    assert_eq!(table.width(), 30);
+
+    let mut grid = vec![vec!['.'; 30]; table.height()];
+    table.draw_to((0, 0), &mut grid);
+
+    // 30 - 5 (separators) - 4 - 3 = 18 cells are shared 1:2.
+    assert_eq!(
+        DisplayGrid::to_string(&grid).unwrap(),
+        concat!(
+            "┌────┬───┬──────┬────────────┐\n",
+            "│fix…│au…│share │      double│\n",
+            "├────┼───┼──────┼────────────┤\n",
+            "│abc…│ a │x     │           y│\n",
+            "└────┴───┴──────┴────────────┘\n",
+        )
+    );
+
+    // Without a width, proportional columns fit their text.
+    let table = Table::new(["a", "bb"]).with_column_width(1, ColumnWidth::Proportional(1));
+    assert_eq!(table.width(), 10);
+}
+
+#[test]
+fn layout_fill_weights() {
+    let layout = Layout::new(
+        Orientation::Horizontal,
+        [
+            Constraint::Fill(1),
+            Constraint::Ratio(1, 4),
+            Constraint::Fill(2),
+            Constraint::Min(1),
+        ],
+    )
+    .with_spacing(1);
+    assert_eq!(layout.orientation(), Orientation::Horizontal);
+    assert_eq!(layout.constraints().len(), 4);
+
+    // 23 - 3 (spacing) = 20 cells; the ratio takes 5, the minimum 1, and 14 are filled 1:2.
+    assert_eq!(
+        layout.split(Rect::new(2, 3, 23, 1)),
+        vec![
+            Rect::new(2, 3, 5, 1),
+            Rect::new(8, 3, 5, 1),
+            Rect::new(14, 3, 9, 1),
+            Rect::new(24, 3, 1, 1),
+        ]
+    );
+}
+
+#[test]
+fn layout_overflow() {
+    let layout = Layout::new(
+        Orientation::Vertical,
+        [
+            Constraint::Max(4),
+            Constraint::Length(3),
+            Constraint::Max(2),
+            Constraint::Length(3),
+            Constraint::Ratio(1, 0),
+        ],
+    );
+
+    // 12 cells are wanted, out of 5: the last `Max` shrinks to nothing, then the first to 1.
+    assert_eq!(
+        layout.split(Rect::new(0, 0, 2, 5)),
+        vec![
+            Rect::new(0, 0, 2, 0),
+            Rect::new(0, 0, 2, 3),
+            Rect::new(0, 3, 2, 0),
+            Rect::new(0, 3, 2, 2),
+            Rect::new(0, 5, 2, 0),
+        ]
+    );
+
+    // Margins and spacing larger than the rectangle.
+    let layout = Layout::new(Orientation::Horizontal, [Constraint::Fill(1); 3])
+        .with_margin(2, 0)
+        .with_spacing(5);
+    assert!(layout
+        .split(Rect::new(0, 0, 6, 1))
+        .iter()
+        .all(|rect| rect.width == 0 && rect.x <= 4));
+}
+
+#[test]
+fn viewport_clips_reads_and_writes() {
+    let mut grid = [['.'; 3]; 3];
+    let mut viewport = Viewport::new(&mut grid, Rect::new(1, 1, 2, 1));
+    assert_eq!(viewport.size(), (2, 1));
+    assert_eq!(viewport.rect(), Rect::new(1, 1, 2, 1));
+
+    viewport.set((0, 0), 'a');
+    viewport.set((2, 0), 'b');
+    viewport.set((0, 1), 'c');
+    assert_eq!(viewport.get((0, 0)), Some(&'a'));
+    assert_eq!(viewport.get((1, 0)), Some(&'.'));
+    assert_eq!(viewport.get((0, 1)), None);
+
+    #[rustfmt::skip]
+    assert_eq!(grid, [
+        ['.', '.', '.'],
+        ['.', 'a', '.'],
+        ['.', '.', '.'],
+    ]);
+}
+
+#[test]
+fn flex_column_justify_and_align() {
+    let column = Flex::column()
+        .with_child(FillRect::new(1, 1, 'a'))
+        .with_child(FillRect::new(2, 1, 'b'))
+        .with_child(FillRect::new(3, 1, 'c'))
+        .with_justify(FlexJustify::SpaceEvenly)
+        .with_align(FlexAlign::End)
+        .with_size(3, 7);
+    assert_eq!(column.len(), 3);
+    assert_eq!((column.width(), column.height()), (3, 7));
+
+    let mut grid = [['.'; 3]; 7];
+    column.draw_to((0, 0), &mut grid);
+
+    #[rustfmt::skip]
+    assert_eq!(grid, [
+        ['.', '.', '.'],
+        ['.', '.', 'a'],
+        ['.', '.', '.'],
+        ['.', 'b', 'b'],
+        ['.', '.', '.'],
+        ['c', 'c', 'c'],
+        ['.', '.', '.'],
+    ]);
+}
+
+#[test]
+fn flex_stack_overlaps_and_clips() {
+    let empty = Flex::<char>::stack();
+    assert!(empty.is_empty());
+    assert_eq!((empty.width(), empty.height()), (0, 0));
+
+    let stack = Flex::stack()
+        .with_child(FillRect::new(5, 1, '-'))
+        .with_child(FillRect::new(1, 1, 'o'))
+        .with_align(FlexAlign::Center)
+        .with_size(3, 3);
+
+    let mut grid = [['.'; 5]; 5];
+    stack.draw_to((1, 1), &mut grid);
+
+    #[rustfmt::skip]
+    assert_eq!(grid, [
+        ['.', '.', '.', '.', '.'],
+        ['.', '.', '.', '.', '.'],
+        ['.', '-', 'o', '-', '.'],
+        ['.', '.', '.', '.', '.'],
+        ['.', '.', '.', '.', '.'],
+    ]);
+}
+
+#[test]
+fn list_state_navigation() {
+    let mut state = ListState::new();
+    state.select_previous(4);
+    assert_eq!(state.selected(), Some(3));
+    state.select_next(4);
+    assert_eq!(state.selected(), Some(3));
+    state.select(Some(0));
+    state.select_previous(4);
+    assert_eq!(state.selected(), Some(0));
+    state.select_next(0);
+    assert_eq!(state.selected(), None);
+
+    // Scrolling only moves the view when the selection leaves it.
+    let mut state = ListState::new().with_selected(Some(5));
+    state.scroll_into_view(10, 3);
+    assert_eq!(state.offset(), 3);
+    state.select_previous(10);
+    state.scroll_into_view(10, 3);
+    assert_eq!(state.offset(), 3);
+    state.select(Some(1));
+    state.scroll_into_view(10, 3);
+    assert_eq!(state.offset(), 1);
+
+    // The offset is clamped to the items, even without a selection.
+    let mut state = ListState::new();
+    state.set_offset(8);
+    state.scroll_into_view(4, 3);
+    assert_eq!(state.offset(), 1);
+}
+
+#[test]
+fn list_scrolls_marks_and_truncates() {
+    let bold = Style::new().bold();
+    let list = List::new(6, 3, ["a", "bb", "ccc", "dddd", "strawberry"])
+        .with_markers("*", "")
+        .with_alignment(Alignment::Center)
+        .with_highlight(bold)
+        .with_state(ListState::new().with_selected(Some(9)));
+    assert_eq!(list.items().len(), 5);
+
+    let mut grid = [[Styled::from('.'); 6]; 4];
+    list.draw_to((0, 1), &mut grid);
+
+    let rows = grid.map(|row| String::from_iter(row.map(|cell| cell.value)));
+    assert_eq!(rows, ["......", "  ccc ", " dddd ", "*stra…"]);
+    assert!(grid[3].iter().all(|cell| cell.style == bold));
+    assert!(grid[2].iter().all(|cell| cell.style.is_default()));
+
+    // Drawing does not change the state.
+    assert_eq!(list.state(), &ListState::new().with_selected(Some(9)));
+}
@@ -0,0 +1,262 @@
+//! Parse multi-line text back into grids; the counterpart to [`DisplayGrid`][`crate::DisplayGrid`].
+//!
+//! [`grux::text`][`crate::text`] provides:
+//! - A uniform interface for creating a grid of characters from text: [`FromText`].
+//! - Options for padding ragged lines and expanding tabs: [`TextOptions`].
+//!
+//! This makes it possible to round-trip grids through text, e.g. for test fixtures, or to load
+//! levels from text files.
+//!
+//! # Examples
+//!
+//! ```
+//! # #[cfg(feature = "std")]
+//! # {
+//! use grux::DisplayGrid;
+//! use grux::text::FromText;
+//!
+//! let text = "╔═╗\n║ ║\n╚═╝\n";
+//! let grid = <[[char; 3]; 3]>::from_text(text).unwrap();
+//!
+//! assert_eq!(grid[1], ['║', ' ', '║']);
+//! assert_eq!(grid.to_string().unwrap(), text);
+//! # }
+//! ```
+
+use alloc::vec::Vec;
+use core::{error::Error, fmt};
+
+use crate::grid::Grid;
+
+/// Options for parsing text into a grid.
+///
+/// # Examples
+///
+/// ```
+/// # use grux::text::{FromText, TextOptions};
+/// let options = TextOptions {
+///     pad: Some('.'),
+///     tab_width: Some(4),
+/// };
+///
+/// let grid = Vec::<Vec<char>>::from_text_with("a\tb\nc", &options).unwrap();
+///
+/// assert_eq!(grid, vec![
+///     vec!['a', ' ', ' ', ' ', 'b'],
+///     vec!['c', '.', '.', '.', '.'],
+/// ]);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TextOptions {
+    /// The character that lines (and missing rows) are padded with to make the grid rectangular;
+    /// defaults to `' '`.
+    ///
+    /// If `None`, lines are not padded: nested vectors are left ragged, and other grids return an
+    /// error if the lines do not all have the same length.
+    pub pad: Option<char>,
+
+    /// If set, tabs are expanded to spaces up to the next multiple of this many columns; defaults
+    /// to `None`, i.e. tabs are kept as-is.
+    pub tab_width: Option<usize>,
+}
+
+impl Default for TextOptions {
+    fn default() -> Self {
+        Self {
+            pad: Some(' '),
+            tab_width: None,
+        }
+    }
+}
+
+/// An error that occurs when parsing text into a grid.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ParseGridError {
+    /// The text does not fit the size of a fixed-size grid.
+    SizeMismatch {
+        /// The `(width, height)` of the grid.
+        expected: (usize, usize),
+
+        /// The `(width, height)` of the text, i.e. the longest line and the number of lines.
+        actual: (usize, usize),
+    },
+
+    /// A line is shorter than the longest line, and padding was disabled.
+    RaggedLine {
+        /// The (zero-based) index of the line.
+        line: usize,
+
+        /// The length of the longest line.
+        expected: usize,
+
+        /// The length of the line.
+        actual: usize,
+    },
+}
+
+impl fmt::Display for ParseGridError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseGridError::SizeMismatch { expected, actual } => write!(
+                f,
+                "expected a {}x{} grid, but the text is {}x{}",
+                expected.0, expected.1, actual.0, actual.1
+            ),
+            ParseGridError::RaggedLine {
+                line,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "expected line {} to be {} characters long, but it is {}",
+                line, expected, actual
+            ),
+        }
+    }
+}
+
+impl Error for ParseGridError {}
+
+/// A trait for grids of characters that can be created from multi-line text.
+///
+/// Each line of the text is a row, and each character is a column. A trailing newline (as written
+/// by [`DisplayGrid`][`crate::DisplayGrid`]) does not start a new row, and `\r\n` is treated as
+/// `\n`.
+pub trait FromText: Sized {
+    /// Parses the text into a grid, using the default [`TextOptions`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the text does not fit the grid.
+    fn from_text(text: &str) -> Result<Self, ParseGridError> {
+        Self::from_text_with(text, &TextOptions::default())
+    }
+
+    /// Parses the text into a grid, using the given options.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the text does not fit the grid.
+    fn from_text_with(text: &str, options: &TextOptions) -> Result<Self, ParseGridError>;
+}
+
+/// Splits text into lines of characters, expanding tabs.
+fn parse_lines(text: &str, options: &TextOptions) -> Vec<Vec<char>> {
+    text.lines()
+        .map(|line| {
+            let mut row = Vec::with_capacity(line.len());
+            for c in line.chars() {
+                match (c, options.tab_width) {
+                    ('\t', Some(width)) if width > 0 => {
+                        row.resize((row.len() / width + 1) * width, ' ');
+                    }
+                    (c, _) => row.push(c),
+                }
+            }
+            row
+        })
+        .collect()
+}
+
+/// Returns the `(width, height)` of the lines, i.e. the longest line and the number of lines.
+fn size_of(lines: &[Vec<char>]) -> (usize, usize) {
+    (lines.iter().map(Vec::len).max().unwrap_or(0), lines.len())
+}
+
+/// Pads every line to the given width, or returns an error if padding is disabled.
+fn pad_lines(
+    lines: &mut [Vec<char>],
+    width: usize,
+    options: &TextOptions,
+) -> Result<(), ParseGridError> {
+    for (i, line) in lines.iter_mut().enumerate() {
+        match options.pad {
+            Some(pad) => line.resize(width, pad),
+            None if line.len() != width => {
+                return Err(ParseGridError::RaggedLine {
+                    line: i,
+                    expected: width,
+                    actual: line.len(),
+                });
+            }
+            None => {}
+        }
+    }
+    Ok(())
+}
+
+/// Parses text into a growable nested vector of characters.
+///
+/// Never fails; if padding is disabled, the rows are as long as their lines.
+impl FromText for Vec<Vec<char>> {
+    fn from_text_with(text: &str, options: &TextOptions) -> Result<Self, ParseGridError> {
+        let mut lines = parse_lines(text, options);
+        if options.pad.is_some() {
+            let (width, _) = size_of(&lines);
+            pad_lines(&mut lines, width, options)?;
+        }
+        Ok(lines)
+    }
+}
+
+/// Parses text into a fixed-size nested array of characters.
+///
+/// Returns [`ParseGridError::SizeMismatch`] if the text is wider or taller than the array, or if
+/// padding is disabled and the text is narrower or shorter than the array.
+impl<const W: usize, const H: usize> FromText for [[char; W]; H] {
+    fn from_text_with(text: &str, options: &TextOptions) -> Result<Self, ParseGridError> {
+        let lines = parse_lines(text, options);
+        let actual = size_of(&lines);
+        let mismatch = Err(ParseGridError::SizeMismatch {
+            expected: (W, H),
+            actual,
+        });
+        if actual.0 > W || actual.1 > H {
+            return mismatch;
+        }
+
+        let mut grid = [[options.pad.unwrap_or(' '); W]; H];
+        for (row, line) in grid.iter_mut().zip(lines.iter()) {
+            if options.pad.is_none() && line.len() != W {
+                return mismatch;
+            }
+            row[..line.len()].copy_from_slice(line);
+        }
+        if options.pad.is_none() && actual.1 != H {
+            return mismatch;
+        }
+        Ok(grid)
+    }
+}
+
+/// Parses text into a flat grid of characters, as wide as the longest line.
+///
+/// Returns [`ParseGridError::RaggedLine`] if padding is disabled and the lines are not all the
+/// same length.
+impl FromText for Grid<char> {
+    fn from_text_with(text: &str, options: &TextOptions) -> Result<Self, ParseGridError> {
+        let mut lines = parse_lines(text, options);
+        let (width, height) = size_of(&lines);
+        pad_lines(&mut lines, width, options)?;
+        Ok(Grid::from_cells(width, height, lines.concat()))
+    }
+}
+
+/// Parses text into a flat grid of characters, using the default [`TextOptions`].
+///
+/// # Examples
+///
+/// ```
+/// # use grux::grid::Grid;
+/// let grid: Grid<char> = "ab\nc".parse().unwrap();
+///
+/// assert_eq!(grid.size(), (2, 2));
+/// assert_eq!(grid.cells(), &['a', 'b', 'c', ' ']);
+/// ```
+impl core::str::FromStr for Grid<char> {
+    type Err = ParseGridError;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        Self::from_text(text)
+    }
+}
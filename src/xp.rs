@@ -0,0 +1,361 @@
+//! Import and export [REXPaint](https://www.gridsagegames.com/rexpaint/) `.xp` images.
+//!
+//! [`grux::xp`][`crate::xp`] provides:
+//! - A layered grid of styled cells, read from and written to `.xp` files: [`XpImage`].
+//! - A sprite that draws the visible cells of a layer: [`LayerSprite`].
+//!
+//! Glyphs are converted between [code page 437][] and Unicode, so art drawn with box-drawing or
+//! block characters in REXPaint can be used directly with the rest of this crate, and colors are
+//! converted to and from [`Color::Rgb`]. Cells with REXPaint's transparent background (magenta,
+//! i.e. `#ff00ff`) have no background color.
+//!
+//! Requires the `xp` feature.
+//!
+//! [code page 437]: https://en.wikipedia.org/wiki/Code_page_437
+//!
+//! # Examples
+//!
+//! ```
+//! use grux::GridWriter;
+//! use grux::art::Sprite;
+//! use grux::style::{Color, Style, Styled};
+//! use grux::xp::XpImage;
+//!
+//! let mut image = XpImage::new(2, 1, 1);
+//! let heart = Styled::new('♥', Style::new().fg(Color::Rgb(255, 0, 0)).bg(Color::Rgb(0, 0, 0)));
+//! image.layer_mut(0).unwrap().set((0, 0), heart);
+//!
+//! // Round-trip the image through the `.xp` format.
+//! let mut file = Vec::new();
+//! image.write(&mut file).unwrap();
+//! let image = XpImage::read(&mut file.as_slice()).unwrap();
+//!
+//! // Draw the visible (i.e. non-transparent) cells of the first layer.
+//! let mut grid = [[Styled::from('.'); 3]; 1];
+//! image.layer_sprite(0).unwrap().draw_to((1, 0), &mut grid);
+//!
+//! assert_eq!(grid[0][1], heart);
+//! assert_eq!(grid[0][2].value, '.');
+//! ```
+
+use std::io::{self, Read, Write};
+
+use flate2::{read::GzDecoder, write::GzEncoder, Compression};
+
+use crate::{
+    art::Sprite,
+    grid::Grid,
+    style::{Color, Style, Styled},
+    GridWriter,
+};
+
+/// The background color REXPaint uses for transparent cells.
+const TRANSPARENT: (u8, u8, u8) = (255, 0, 255);
+
+/// The foreground color written for cells without a foreground color.
+const DEFAULT_FOREGROUND: (u8, u8, u8) = (255, 255, 255);
+
+/// The version written to `.xp` files, matching REXPaint 1.x.
+const VERSION: i32 = -1;
+
+/// The number of bytes of a cell: a 32-bit glyph, and `(red, green, blue)` foreground and
+/// background colors.
+const CELL_BYTES: usize = 10;
+
+/// A REXPaint image: one or more layers of styled cells, all of the same size.
+///
+/// Layers are ordered from bottom to top. Only the glyph and the colors of a cell are stored in a
+/// `.xp` file; other attributes, such as [`Style::bold`], are lost when writing.
+///
+/// # Examples
+///
+/// ```
+/// # use grux::xp::XpImage;
+/// let image = XpImage::new(80, 25, 2);
+///
+/// assert_eq!(image.size(), (80, 25));
+/// assert_eq!(image.layers().len(), 2);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct XpImage {
+    width: usize,
+    height: usize,
+    layers: Vec<Grid<Styled>>,
+}
+
+impl XpImage {
+    /// Creates an image of the given size, with the given number of transparent layers.
+    #[must_use]
+    pub fn new(width: usize, height: usize, layers: usize) -> Self {
+        Self {
+            width,
+            height,
+            layers: vec![Grid::new(width, height, Styled::from(' ')); layers],
+        }
+    }
+
+    /// Creates an image from the given layers, ordered from bottom to top.
+    ///
+    /// # Panics
+    ///
+    /// If there are no layers, or the layers are not all the same size.
+    #[must_use]
+    pub fn from_layers(layers: Vec<Grid<Styled>>) -> Self {
+        let (width, height) = layers.first().expect("Image must have a layer").size();
+        assert!(
+            layers.iter().all(|layer| layer.size() == (width, height)),
+            "Layers must all be the same size"
+        );
+        Self {
+            width,
+            height,
+            layers,
+        }
+    }
+
+    /// Returns the `(width, height)` of the image.
+    #[must_use]
+    pub fn size(&self) -> (usize, usize) {
+        (self.width, self.height)
+    }
+
+    /// Returns the layers of the image, ordered from bottom to top.
+    #[must_use]
+    pub fn layers(&self) -> &[Grid<Styled>] {
+        &self.layers
+    }
+
+    /// Returns the layer at the given index, or `None` if it does not exist.
+    #[must_use]
+    pub fn layer(&self, index: usize) -> Option<&Grid<Styled>> {
+        self.layers.get(index)
+    }
+
+    /// Returns the layer at the given index mutably, or `None` if it does not exist.
+    #[must_use]
+    pub fn layer_mut(&mut self, index: usize) -> Option<&mut Grid<Styled>> {
+        self.layers.get_mut(index)
+    }
+
+    /// Adds a layer on top of the existing layers.
+    ///
+    /// # Panics
+    ///
+    /// If the layer is not the same size as the image.
+    pub fn push_layer(&mut self, layer: Grid<Styled>) {
+        assert_eq!(
+            layer.size(),
+            self.size(),
+            "Layer must be the same size as the image"
+        );
+        self.layers.push(layer);
+    }
+
+    /// Returns a sprite that draws the visible cells of the layer at the given index, or `None` if
+    /// it does not exist.
+    #[must_use]
+    pub fn layer_sprite(&self, index: usize) -> Option<LayerSprite<'_>> {
+        self.layer(index).map(|layer| LayerSprite { layer })
+    }
+
+    /// Flattens the layers into a single grid, as REXPaint displays them.
+    ///
+    /// Each cell is taken from the topmost layer where it is visible; cells that are transparent in
+    /// every layer are left as an unstyled space.
+    #[must_use]
+    pub fn flatten(&self) -> Grid<Styled> {
+        let mut grid = Grid::new(self.width, self.height, Styled::from(' '));
+        for layer in &self.layers {
+            LayerSprite { layer }.draw_to((0, 0), &mut grid);
+        }
+        grid
+    }
+
+    /// Reads a gzip-compressed `.xp` image from the given input stream.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the input stream returns an error, or if the image is malformed.
+    pub fn read(stream: &mut impl Read) -> io::Result<Self> {
+        let mut bytes = Vec::new();
+        GzDecoder::new(stream).read_to_end(&mut bytes)?;
+        let mut bytes = bytes.as_slice();
+
+        let _version = read_i32(&mut bytes)?;
+        let count = read_size(&mut bytes)?;
+        let mut layers = Vec::new();
+        for _ in 0..count {
+            let width = read_size(&mut bytes)?;
+            let height = read_size(&mut bytes)?;
+            let cells = width
+                .checked_mul(height)
+                .filter(|&cells| cells.saturating_mul(CELL_BYTES) <= bytes.len())
+                .ok_or_else(|| invalid("layer is larger than the file"))?;
+
+            // Cells are stored in column-major order.
+            let mut layer = Grid::new(width, height, Styled::from(' '));
+            for i in 0..cells {
+                let glyph = u32::from_le_bytes(take(&mut bytes)?);
+                let [fr, fg, fb, br, bg, bb] = take(&mut bytes)?;
+                let value = u8::try_from(glyph)
+                    .map(|glyph| CP437[usize::from(glyph)])
+                    .map_err(|_| invalid("glyph is outside of code page 437"))?;
+                let background = (br, bg, bb);
+                let style = Style {
+                    foreground: Some(Color::Rgb(fr, fg, fb)),
+                    background: (background != TRANSPARENT).then_some(Color::Rgb(br, bg, bb)),
+                    ..Style::default()
+                };
+                layer.set((i / height, i % height), Styled::new(value, style));
+            }
+            layers.push(layer);
+        }
+
+        let (width, height) = layers.first().map_or((0, 0), Grid::size);
+        if layers.iter().any(|layer| layer.size() != (width, height)) {
+            return Err(invalid("layers are not all the same size"));
+        }
+        Ok(Self {
+            width,
+            height,
+            layers,
+        })
+    }
+
+    /// Writes the image as a gzip-compressed `.xp` file into the given output stream.
+    ///
+    /// Characters outside of code page 437 are written as `?`, cells without a foreground color are
+    /// written as white, and cells without a background color are written as transparent.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the output stream returns an error, or the image is too large.
+    pub fn write(&self, stream: &mut impl Write) -> io::Result<()> {
+        let size = |n: usize| i32::try_from(n).map_err(|_| invalid("image is too large"));
+        let mut encoder = GzEncoder::new(stream, Compression::default());
+        encoder.write_all(&VERSION.to_le_bytes())?;
+        encoder.write_all(&size(self.layers.len())?.to_le_bytes())?;
+        for layer in &self.layers {
+            encoder.write_all(&size(self.width)?.to_le_bytes())?;
+            encoder.write_all(&size(self.height)?.to_le_bytes())?;
+            for x in 0..self.width {
+                for y in 0..self.height {
+                    let Styled { value, style } = &layer.cells()[y * self.width + x];
+                    let (fr, fg, fb) = style.foreground.map_or(DEFAULT_FOREGROUND, |c| c.to_rgb());
+                    let (br, bg, bb) = style.background.map_or(TRANSPARENT, |c| c.to_rgb());
+                    encoder.write_all(&u32::from(to_cp437(*value)).to_le_bytes())?;
+                    encoder.write_all(&[fr, fg, fb, br, bg, bb])?;
+                }
+            }
+        }
+        encoder.finish()?;
+        Ok(())
+    }
+}
+
+/// A sprite that draws the visible cells of a layer of an [`XpImage`].
+///
+/// Transparent cells, i.e. cells without a background color, are skipped, so layers can be drawn
+/// on top of existing content.
+///
+/// # Examples
+///
+/// ```
+/// # use grux::GridWriter;
+/// # use grux::art::Sprite;
+/// # use grux::grid::Grid;
+/// # use grux::style::{Color, Style, Styled};
+/// # use grux::xp::XpImage;
+/// let mut layer = Grid::new(2, 1, Styled::from(' '));
+/// layer.set((1, 0), Styled::new('#', Style::new().bg(Color::Blue)));
+///
+/// let image = XpImage::from_layers(vec![layer]);
+/// let mut grid = [[Styled::from('.'); 2]; 1];
+/// image.layer_sprite(0).unwrap().draw_to((0, 0), &mut grid);
+///
+/// assert_eq!(grid[0][0].value, '.');
+/// assert_eq!(grid[0][1].value, '#');
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct LayerSprite<'a> {
+    layer: &'a Grid<Styled>,
+}
+
+impl Sprite for LayerSprite<'_> {
+    type Element = Styled;
+
+    fn width(&self) -> usize {
+        self.layer.size().0
+    }
+
+    fn height(&self) -> usize {
+        self.layer.size().1
+    }
+
+    fn draw_to(&self, position: (usize, usize), to: &mut impl GridWriter<Element = Self::Element>) {
+        let (x, y) = position;
+        for (row, cells) in self.layer.rows().enumerate() {
+            for (column, cell) in cells.iter().enumerate() {
+                if cell.style.background.is_some() {
+                    to.set((x + column, y + row), *cell);
+                }
+            }
+        }
+    }
+}
+
+/// Returns an error for malformed `.xp` data.
+fn invalid(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+/// Takes the next `N` bytes, or returns an error if there are not enough bytes.
+fn take<const N: usize>(bytes: &mut &[u8]) -> io::Result<[u8; N]> {
+    let (head, tail) = bytes
+        .split_first_chunk()
+        .ok_or_else(|| invalid("unexpected end of file"))?;
+    *bytes = tail;
+    Ok(*head)
+}
+
+/// Takes the next little-endian 32-bit signed integer.
+fn read_i32(bytes: &mut &[u8]) -> io::Result<i32> {
+    take(bytes).map(i32::from_le_bytes)
+}
+
+/// Takes the next little-endian 32-bit signed integer, which must not be negative.
+fn read_size(bytes: &mut &[u8]) -> io::Result<usize> {
+    usize::try_from(read_i32(bytes)?).map_err(|_| invalid("size is negative"))
+}
+
+/// Converts a character to code page 437, or `?` if it is not in the code page.
+fn to_cp437(c: char) -> u8 {
+    match c {
+        ' '..='~' => c as u8,
+        _ => CP437
+            .iter()
+            .position(|&other| other == c)
+            .and_then(|i| u8::try_from(i).ok())
+            .unwrap_or(b'?'),
+    }
+}
+
+/// The Unicode characters of code page 437, as displayed by REXPaint; `0` is displayed as a space.
+#[rustfmt::skip]
+const CP437: [char; 256] = [
+    ' ', '☺', '☻', '♥', '♦', '♣', '♠', '•', '◘', '○', '◙', '♂', '♀', '♪', '♫', '☼',
+    '►', '◄', '↕', '‼', '¶', '§', '▬', '↨', '↑', '↓', '→', '←', '∟', '↔', '▲', '▼',
+    ' ', '!', '"', '#', '$', '%', '&', '\'', '(', ')', '*', '+', ',', '-', '.', '/',
+    '0', '1', '2', '3', '4', '5', '6', '7', '8', '9', ':', ';', '<', '=', '>', '?',
+    '@', 'A', 'B', 'C', 'D', 'E', 'F', 'G', 'H', 'I', 'J', 'K', 'L', 'M', 'N', 'O',
+    'P', 'Q', 'R', 'S', 'T', 'U', 'V', 'W', 'X', 'Y', 'Z', '[', '\\', ']', '^', '_',
+    '`', 'a', 'b', 'c', 'd', 'e', 'f', 'g', 'h', 'i', 'j', 'k', 'l', 'm', 'n', 'o',
+    'p', 'q', 'r', 's', 't', 'u', 'v', 'w', 'x', 'y', 'z', '{', '|', '}', '~', '⌂',
+    'Ç', 'ü', 'é', 'â', 'ä', 'à', 'å', 'ç', 'ê', 'ë', 'è', 'ï', 'î', 'ì', 'Ä', 'Å',
+    'É', 'æ', 'Æ', 'ô', 'ö', 'ò', 'û', 'ù', 'ÿ', 'Ö', 'Ü', '¢', '£', '¥', '₧', 'ƒ',
+    'á', 'í', 'ó', 'ú', 'ñ', 'Ñ', 'ª', 'º', '¿', '⌐', '¬', '½', '¼', '¡', '«', '»',
+    '░', '▒', '▓', '│', '┤', '╡', '╢', '╖', '╕', '╣', '║', '╗', '╝', '╜', '╛', '┐',
+    '└', '┴', '┬', '├', '─', '┼', '╞', '╟', '╚', '╔', '╩', '╦', '╠', '═', '╬', '╧',
+    '╨', '╤', '╥', '╙', '╘', '╒', '╓', '╫', '╪', '┘', '┌', '█', '▄', '▌', '▐', '▀',
+    'α', 'ß', 'Γ', 'π', 'Σ', 'σ', 'µ', 'τ', 'Φ', 'Θ', 'Ω', 'δ', '∞', 'φ', 'ε', '∩',
+    '≡', '±', '≥', '≤', '⌠', '⌡', '÷', '≈', '°', '∙', '·', '√', 'ⁿ', '²', '■', '\u{a0}',];